@@ -30,7 +30,7 @@
 use embedded_hal_async::{delay::DelayNs, i2c::*};
 use micromath::vector::Vector3d;
 
-use super::{defs::*, Bmi323, Error};
+use super::{defs::*, Bmi323, ConfigError, Error, Sensor};
 
 impl<I, D, W, E> Bmi323<I, D, W>
 where
@@ -41,9 +41,25 @@ where
     self.read(Reg::AccConf).await
   }
 
+  /// Set the accelerometer configuration.
+  ///
+  /// The configuration is checked with [`AccelConfig::validate`] before
+  /// writing. Afterwards `ERR_REG.acc_conf_err` is checked, so a combination
+  /// the device rejects is reported as
+  /// [`ConfigError::SensorConfRejected`] instead of a data-ready timeout.
+  /// Unless the accelerometer is being disabled, the function then waits for
+  /// the first sample.
   pub async fn set_accel_conf(&mut self, cfg: AccelConfig) -> Result<(), Error<E>> {
+    cfg.validate().map_err(Error::Config)?;
     self.write(Reg::AccConf, cfg).await?;
-    self.wait_for(crate::Sensor::Accel).await
+    if self.get_error().await?.acc_conf_err {
+      return Err(Error::Config(ConfigError::SensorConfRejected(Sensor::Accel)));
+    }
+    if cfg.mode != AccelPowerMode::Disable {
+      self.wait_for(Sensor::Accel).await?;
+    }
+    self.snapshot.accel = Some(cfg);
+    Ok(())
   }
 
   /// Read raw accelerometer data (16-bit signed integers).
@@ -52,7 +68,7 @@ where
   /// to get scaled values in g units.
   pub async fn get_raw_accel_data(&mut self) -> Result<Vector3d<i16>, Error<E>> {
     let xyz: crate::XYZ = self.read(Reg::AccDataX).await?;
-    Ok(Vector3d { x: xyz.x, y: xyz.y, z: xyz.z })
+    Ok(self.to_board_raw(Vector3d { x: xyz.x, y: xyz.y, z: xyz.z }))
   }
 
   /// Read accelerometer data scaled to g units.
@@ -60,10 +76,10 @@ where
   /// Returns acceleration in g (standard gravity, 9.81 m/s²) for each axis.
   /// The scaling is automatically applied based on the configured range.
   pub async fn get_accel_data(&mut self) -> Result<Vector3d<f32>, Error<E>> {
-    let accel_data = self.get_raw_accel_data().await?;
+    let xyz: crate::XYZ = self.read(Reg::AccDataX).await?;
     let range = self.get_accel_conf().await?.range.multiplier();
 
-    Ok(Vector3d { x: accel_data.x as f32 * range, y: accel_data.y as f32 * range, z: accel_data.z as f32 * range })
+    Ok(self.to_board(Vector3d { x: xyz.x as f32 * range, y: xyz.y as f32 * range, z: xyz.z as f32 * range }))
   }
 }
 
@@ -73,6 +89,7 @@ where
 /// bandwidth, averaging, and power mode.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 2)]
 pub struct AccelConfig {
   /// Output Data Rate in Hz (≈0.78 Hz to 6.4 kHz).
@@ -93,6 +110,23 @@ pub struct AccelConfig {
   pub mode: AccelPowerMode,
 }
 
+impl AccelConfig {
+  /// Check power mode, ODR and averaging against the datasheet rules.
+  ///
+  /// - Low-power mode: ODR ≤ 400 Hz and `ODR × averaged samples ≤ 400 Hz`.
+  /// - Normal and high-performance modes: ODR ≥ 12.5 Hz.
+  /// - A disabled accelerometer is always valid.
+  pub fn validate(&self) -> Result<(), ConfigError> {
+    match self.mode {
+      AccelPowerMode::Disable => Ok(()),
+      AccelPowerMode::LowPower => crate::types::check_odr_mode(Sensor::Accel, true, self.odr, self.avg),
+      AccelPowerMode::Normal | AccelPowerMode::HighPerf => {
+        crate::types::check_odr_mode(Sensor::Accel, false, self.odr, self.avg)
+      }
+    }
+  }
+}
+
 impl Default for AccelConfig {
   fn default() -> Self {
     AccelConfig {
@@ -112,6 +146,7 @@ impl Default for AccelConfig {
 /// lower resolution.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccelRange {
   /// ±2g range
   G2 = 0x00,
@@ -156,6 +191,7 @@ impl TryFrom<u8> for AccelRange {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccelPowerMode {
   Disable = 0x0,
   LowPower = 0x3,
@@ -1,6 +1,35 @@
+//! Alternate sensor configurations and automatic switching.
+//!
+//! The BMI323 holds a second ("alternate") accelerometer/gyroscope
+//! configuration next to the user one. The feature engine can swap between
+//! the two when a feature fires, e.g. run the accelerometer in low-power mode
+//! until any-motion triggers, then switch to a high-rate configuration until
+//! no-motion triggers.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::accel::{AccelConfig, AccelPowerMode};
+//! use bmi323::alt::{AltAutoConfig, AltSensorConfig, AltSwitchSource};
+//! use bmi323::OutputDataRate;
+//!
+//! let idle = AccelConfig { odr: OutputDataRate::Hz50, mode: AccelPowerMode::LowPower, ..AccelConfig::default() };
+//! let active = AccelConfig { odr: OutputDataRate::Hz400, mode: AccelPowerMode::HighPerf, ..AccelConfig::default() };
+//! imu
+//!   .configure_auto_power_switch(
+//!     AltSensorConfig::accel(idle),
+//!     AltSensorConfig::accel(active),
+//!     AltAutoConfig::new(AltSwitchSource::AnyMotion, AltSwitchSource::NoMotion),
+//!   )
+//!   .await
+//!   .unwrap();
+//! # }
+//! ```
+
 use embedded_hal_async::{delay::DelayNs, i2c::*};
 
-use super::{Bmi323, Error, defs::*};
+use super::{accel::AccelConfig, defs::*, gyro::GyroConfig, Bmi323, Error, FeatureAddr};
 
 impl<I, D, W, E> Bmi323<I, D, W>
 where
@@ -9,27 +38,200 @@ where
 {
   /// Configure alternate config switching behavior (accel/gyro, reset-on-user-write).
   pub async fn set_alt_enable(&mut self, en: AltEnable) -> Result<(), Error<E>> {
-    self.write(Reg::AltConf, en).await
+    self.write(Reg::AltConf, en).await?;
+    self.snapshot.alt_enable = Some(en);
+    Ok(())
   }
 
   /// Program alternate accelerometer configuration (does not activate by itself).
-  pub async fn set_alt_accel_conf(&mut self, cfg: crate::accel::AccelConfig) -> Result<(), Error<E>> {
-    self.write(Reg::AltAccConf, cfg).await
+  pub async fn set_alt_accel_conf(&mut self, cfg: AccelConfig) -> Result<(), Error<E>> {
+    cfg.validate().map_err(Error::Config)?;
+    self.write(Reg::AltAccConf, cfg).await?;
+    self.snapshot.alt_accel = Some(cfg);
+    Ok(())
   }
 
   /// Program alternate gyroscope configuration (does not activate by itself).
-  pub async fn set_alt_gyro_conf(&mut self, cfg: crate::gyro::GyroConfig) -> Result<(), Error<E>> {
-    self.write(Reg::AltGyrConf, cfg).await
+  pub async fn set_alt_gyro_conf(&mut self, cfg: GyroConfig) -> Result<(), Error<E>> {
+    cfg.validate().map_err(Error::Config)?;
+    self.write(Reg::AltGyrConf, cfg).await?;
+    self.snapshot.alt_gyro = Some(cfg);
+    Ok(())
   }
 
   /// Read active sources for accel/gyro configs. Returns (accel_uses_alt, gyro_uses_alt).
   pub async fn get_alt_status(&mut self) -> Result<AltStatus, Error<E>> {
     self.read(Reg::AltStatus).await
   }
+
+  /// Select which features switch between user and alternate configuration.
+  ///
+  /// Requires an enabled feature engine. The trigger features themselves must
+  /// be configured and enabled separately (e.g. with `enable_any_motion`).
+  pub async fn set_alt_auto_conf(&mut self, cfg: AltAutoConfig) -> Result<(), Error<E>> {
+    self.write_feature(FeatureAddr::AltAutoConfig, cfg).await?;
+    self.snapshot.alt_auto = Some(cfg);
+    Ok(())
+  }
+
+  /// Read the automatic switching sources.
+  pub async fn get_alt_auto_conf(&mut self) -> Result<AltAutoConfig, Error<E>> {
+    self.read_feature(FeatureAddr::AltAutoConfig).await
+  }
+
+  /// Set up automatic switching between a user and an alternate configuration in one call.
+  ///
+  /// Sequence:
+  /// - Validate every given sensor configuration (nothing is written on failure).
+  /// - Enable the feature engine if needed and program `triggers`.
+  /// - Write the alternate configurations, then the user configurations.
+  /// - Enable switching for each sensor that has an alternate configuration.
+  ///
+  /// A sensor without an alternate configuration keeps its user configuration
+  /// at all times. The trigger features must be configured and enabled
+  /// separately, as with [`set_alt_auto_conf`](Self::set_alt_auto_conf).
+  pub async fn configure_auto_power_switch(
+    &mut self,
+    user: AltSensorConfig,
+    alt: AltSensorConfig,
+    triggers: AltAutoConfig,
+  ) -> Result<(), Error<E>> {
+    user.validate().map_err(Error::Config)?;
+    alt.validate().map_err(Error::Config)?;
+
+    if !self.is_feature_engine_enabled().await? {
+      self.enable_feature_engine().await?;
+    }
+    self.set_alt_auto_conf(triggers).await?;
+
+    if let Some(cfg) = alt.accel {
+      self.set_alt_accel_conf(cfg).await?;
+    }
+    if let Some(cfg) = alt.gyro {
+      self.set_alt_gyro_conf(cfg).await?;
+    }
+    if let Some(cfg) = user.accel {
+      self.set_accel_conf(cfg).await?;
+    }
+    if let Some(cfg) = user.gyro {
+      self.set_gyro_conf(cfg).await?;
+    }
+
+    self
+      .set_alt_enable(AltEnable {
+        accel: alt.accel.is_some(),
+        gyro: alt.gyro.is_some(),
+        reset_on_user_conf_write: false,
+      })
+      .await
+  }
+}
+
+/// Accelerometer and/or gyroscope configuration for one side of the switch
+/// (`None`: leave that sensor untouched).
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AltSensorConfig {
+  pub accel: Option<AccelConfig>,
+  pub gyro: Option<GyroConfig>,
+}
+
+impl AltSensorConfig {
+  /// Accelerometer only.
+  pub const fn accel(accel: AccelConfig) -> Self {
+    Self { accel: Some(accel), gyro: None }
+  }
+
+  /// Accelerometer and gyroscope.
+  pub const fn both(accel: AccelConfig, gyro: GyroConfig) -> Self {
+    Self { accel: Some(accel), gyro: Some(gyro) }
+  }
+
+  fn validate(&self) -> Result<(), crate::ConfigError> {
+    if let Some(cfg) = self.accel {
+      cfg.validate()?;
+    }
+    if let Some(cfg) = self.gyro {
+      cfg.validate()?;
+    }
+    Ok(())
+  }
+}
+
+/// Feature block `ALT_AUTO_CONFIG`: which feature events switch the sensors
+/// to the alternate configuration and which switch them back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[packbits::pack(bytes = 2)]
+pub struct AltAutoConfig {
+  /// Feature whose event switches to the alternate configuration.
+  #[bits(4)]
+  pub alt_switch: AltSwitchSource,
+  /// Feature whose event switches back to the user configuration.
+  #[bits(4)]
+  pub user_switch: AltSwitchSource,
+}
+
+impl AltAutoConfig {
+  pub const fn new(alt_switch: AltSwitchSource, user_switch: AltSwitchSource) -> Self {
+    Self { alt_switch, user_switch }
+  }
+}
+
+impl Default for AltAutoConfig {
+  fn default() -> Self {
+    Self::new(AltSwitchSource::None, AltSwitchSource::None)
+  }
+}
+
+/// Feature event used as a switching trigger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AltSwitchSource {
+  None = 0x0,
+  NoMotion = 0x1,
+  AnyMotion = 0x2,
+  Flat = 0x3,
+  Orientation = 0x4,
+  StepDetector = 0x5,
+  StepCounter = 0x6,
+  SigMotion = 0x7,
+  Tilt = 0x8,
+  Tap = 0x9,
+}
+
+impl From<AltSwitchSource> for u8 {
+  fn from(value: AltSwitchSource) -> Self {
+    value as u8
+  }
+}
+
+impl TryFrom<u8> for AltSwitchSource {
+  type Error = ();
+  fn try_from(v: u8) -> Result<Self, Self::Error> {
+    use AltSwitchSource::*;
+    Ok(match v {
+      0x0 => None,
+      0x1 => NoMotion,
+      0x2 => AnyMotion,
+      0x3 => Flat,
+      0x4 => Orientation,
+      0x5 => StepDetector,
+      0x6 => StepCounter,
+      0x7 => SigMotion,
+      0x8 => Tilt,
+      0x9 => Tap,
+      _ => return Err(()),
+    })
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 2)]
 pub struct AltEnable {
   #[bits(1)]
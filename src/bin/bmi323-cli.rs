@@ -0,0 +1,323 @@
+//! Board bring-up tool for a BMI323 on a Linux I2C bus (`linux` feature).
+//!
+//! ```text
+//! bmi323-cli [--bus /dev/i2c-N | --mock] <command> [args]
+//!
+//!   regs                          dump all named registers, decoded
+//!   dump                          full decoded register and feature-memory dump
+//!   accel <odr> <mode> [range]    configure the accelerometer, e.g. `accel 100 normal 8`
+//!   gyro <odr> <mode> [range]     configure the gyroscope, e.g. `gyro 200 highperf 2000`
+//!   selftest                      run the on-chip self-test
+//!   stream [csv|json] [count]     print accel (g) / gyro (dps) samples at the accel ODR
+//!   features [words]              dump the feature-engine memory (default 0x30 words)
+//! ```
+//!
+//! `<odr>` is in Hz (0.78 … 6400), `<mode>` one of `off`, `lowpower`, `normal`,
+//! `highperf` (plus `suspend` for the gyro), `[range]` in g or dps. `--mock`
+//! runs against the in-memory register model instead of hardware.
+
+use std::{fmt::Debug, process::ExitCode, time::Instant};
+
+use bmi323::{
+  accel::{AccelConfig, AccelPowerMode, AccelRange},
+  alt::{AltEnable, AltStatus},
+  fifo::FifoConfig,
+  gyro::{GyroConfig, GyroPowerMode, GyroRange},
+  health::ErrorRegister,
+  interrupt::{FeatureEventExt, IntStatus},
+  io::{IoI2cIf, IoOdrDeviation, IoPadStrength, IoPdnCtrl, IoSpiIf},
+  testing::MockBmi323,
+  Bmi323, FeatureDataStatus, FeatureIo1, Features, OutputDataRate, Reg, SaturationFlags,
+};
+use embedded_hal_async::{
+  delay::DelayNs,
+  i2c::{ErrorType, I2c, Operation, SevenBitAddress},
+};
+use linux_embedded_hal::I2cdev;
+
+const DEFAULT_BUS: &str = "/dev/i2c-1";
+const DEFAULT_FEATURE_WORDS: u16 = 0x30;
+
+#[cfg(not(feature = "events"))]
+type Imu<I> = Bmi323<I, StdDelay>;
+#[cfg(feature = "events")]
+type Imu<I> = Bmi323<I, StdDelay, bmi323::Polling>;
+
+/// Runs a blocking `embedded-hal` bus behind the async traits the driver uses.
+struct Blocking<T>(T);
+
+impl<T: embedded_hal::i2c::ErrorType> ErrorType for Blocking<T> {
+  type Error = T::Error;
+}
+
+impl<T: embedded_hal::i2c::I2c> I2c<SevenBitAddress> for Blocking<T> {
+  async fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+    self.0.transaction(address, operations)
+  }
+}
+
+/// Delay backed by `std::thread::sleep`.
+struct StdDelay;
+
+impl DelayNs for StdDelay {
+  async fn delay_ns(&mut self, ns: u32) {
+    std::thread::sleep(std::time::Duration::from_nanos(ns as u64));
+  }
+}
+
+fn main() -> ExitCode {
+  let args: Vec<String> = std::env::args().skip(1).collect();
+  let mut bus = DEFAULT_BUS.to_string();
+  let mut mock = false;
+  let mut rest = args.as_slice();
+  loop {
+    match rest {
+      [flag, path, tail @ ..] if flag == "--bus" => {
+        bus = path.clone();
+        rest = tail;
+      }
+      [flag, tail @ ..] if flag == "--mock" => {
+        mock = true;
+        rest = tail;
+      }
+      _ => break,
+    }
+  }
+
+  let res = if mock {
+    embassy_futures::block_on(run(&MockBmi323::new(), rest))
+  } else {
+    match I2cdev::new(&bus) {
+      Ok(dev) => embassy_futures::block_on(run(Blocking(dev), rest)),
+      Err(e) => Err(format!("{bus}: {e}")),
+    }
+  };
+  match res {
+    Ok(()) => ExitCode::SUCCESS,
+    Err(e) => {
+      eprintln!("bmi323-cli: {e}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+fn open<I: I2c>(i2c: I) -> Imu<I> {
+  #[cfg(not(feature = "events"))]
+  return Bmi323::new(i2c, StdDelay);
+  #[cfg(feature = "events")]
+  return Bmi323::new(i2c, StdDelay, bmi323::Polling::from_millis(10));
+}
+
+async fn run<I: I2c>(i2c: I, args: &[String]) -> Result<(), String> {
+  let mut imu = open(i2c);
+  let id = imu.get_id().await.map_err(fail)?;
+  if id != 0x43 {
+    return Err(format!("unexpected chip id {id:#04x}"));
+  }
+
+  let args: Vec<&str> = args.iter().map(String::as_str).collect();
+  match args.as_slice() {
+    ["regs"] => dump_registers(&mut imu).await,
+    ["dump"] => {
+      println!("{:#?}", imu.dump_registers().await.map_err(fail)?);
+      Ok(())
+    }
+    ["accel", odr, mode, range @ ..] => {
+      let mut cfg = imu.get_accel_conf().await.map_err(fail)?;
+      cfg.odr = parse_odr(odr)?;
+      cfg.mode = parse_accel_mode(mode)?;
+      if let [range] = range {
+        cfg.range = parse_accel_range(range)?;
+      }
+      imu.set_accel_conf(cfg).await.map_err(fail)?;
+      println!("{cfg:?}");
+      Ok(())
+    }
+    ["gyro", odr, mode, range @ ..] => {
+      let mut cfg = imu.get_gyro_conf().await.map_err(fail)?;
+      cfg.odr = parse_odr(odr)?;
+      cfg.mode = parse_gyro_mode(mode)?;
+      if let [range] = range {
+        cfg.range = parse_gyro_range(range)?;
+      }
+      imu.set_gyro_conf(cfg).await.map_err(fail)?;
+      println!("{cfg:?}");
+      Ok(())
+    }
+    ["selftest"] => {
+      ensure_feature_engine(&mut imu).await?;
+      let result = imu.run_self_test().await.map_err(fail)?;
+      println!("self-test result: {result:#06x}");
+      println!("{:?}", imu.get_feature_io1().await.map_err(fail)?);
+      Ok(())
+    }
+    ["stream", rest @ ..] => {
+      let json = match rest.first() {
+        None | Some(&"csv") => false,
+        Some(&"json") => true,
+        Some(other) => return Err(format!("unknown format `{other}`")),
+      };
+      let count = match rest.get(1) {
+        Some(n) => Some(n.parse::<u64>().map_err(|_| format!("invalid count `{n}`"))?),
+        None => None,
+      };
+      stream(&mut imu, json, count).await
+    }
+    ["features", rest @ ..] => {
+      let words = match rest.first() {
+        Some(n) => parse_u16(n)?,
+        None => DEFAULT_FEATURE_WORDS,
+      };
+      ensure_feature_engine(&mut imu).await?;
+      dump_feature_memory(&mut imu, words).await
+    }
+    _ => Err("usage: bmi323-cli [--bus DEV | --mock] regs|dump|accel|gyro|selftest|stream|features".into()),
+  }
+}
+
+fn fail(e: impl Debug) -> String {
+  format!("{e:?}")
+}
+
+/// Activate the feature engine unless it is already running, as after power-up it is not.
+async fn ensure_feature_engine<I: I2c>(imu: &mut Imu<I>) -> Result<(), String> {
+  if !imu.is_feature_engine_enabled().await.map_err(fail)? {
+    imu.enable_feature_engine().await.map_err(fail)?;
+  }
+  Ok(())
+}
+
+async fn dump_registers<I: I2c>(imu: &mut Imu<I>) -> Result<(), String> {
+  for reg in Reg::ALL {
+    if reg.is_data_port() || reg.is_write_only() {
+      continue;
+    }
+    let raw = imu.read_register(reg).await.map_err(fail)?;
+    let decoded = decode(reg, raw).unwrap_or_default();
+    println!("{:#04x} {:<22} {raw:#06x} {decoded}", reg as u8, reg.name());
+  }
+  Ok(())
+}
+
+/// Decoded view of registers that map to a 16-bit typed struct.
+fn decode(reg: Reg, raw: u16) -> Option<String> {
+  fn show<T: TryFrom<[u8; 2]> + Debug>(raw: u16) -> Option<String> {
+    T::try_from(raw.to_le_bytes()).ok().map(|v| format!("{v:?}"))
+  }
+  match reg {
+    Reg::Err => show::<ErrorRegister>(raw),
+    Reg::SatFlags => show::<SaturationFlags>(raw),
+    Reg::IntStatusInt1 | Reg::IntStatusInt2 => show::<IntStatus>(raw),
+    Reg::FeatureIo0 => show::<Features>(raw),
+    Reg::FeatureIo1 => show::<FeatureIo1>(raw),
+    Reg::AccConf | Reg::AltAccConf => show::<AccelConfig>(raw),
+    Reg::GyrConf | Reg::AltGyrConf => show::<GyroConfig>(raw),
+    Reg::AltConf => show::<AltEnable>(raw),
+    Reg::AltStatus => show::<AltStatus>(raw),
+    Reg::FifoConf => show::<FifoConfig>(raw),
+    Reg::FeatureDataStatus => show::<FeatureDataStatus>(raw),
+    Reg::FeatureEventExt => show::<FeatureEventExt>(raw),
+    Reg::IoPdnCtrl => show::<IoPdnCtrl>(raw),
+    Reg::IoSpiIf => show::<IoSpiIf>(raw),
+    Reg::IoPadStrength => show::<IoPadStrength>(raw),
+    Reg::IoI2cIf => show::<IoI2cIf>(raw),
+    Reg::IoOdrDeviation => show::<IoOdrDeviation>(raw),
+    _ => None,
+  }
+}
+
+async fn dump_feature_memory<I: I2c>(imu: &mut Imu<I>, words: u16) -> Result<(), String> {
+  let mut row = [0u16; 8];
+  for base in (0..words).step_by(row.len()) {
+    let row = &mut row[..(words - base).min(8) as usize];
+    imu.read_feature_words(base, row).await.map_err(fail)?;
+    print!("{base:#06x}:");
+    row.iter().for_each(|w| print!(" {w:04x}"));
+    println!();
+  }
+  Ok(())
+}
+
+async fn stream<I: I2c>(imu: &mut Imu<I>, json: bool, count: Option<u64>) -> Result<(), String> {
+  let odr = imu.get_accel_conf().await.map_err(fail)?.odr.hz();
+  let mut delay = StdDelay;
+  let start = Instant::now();
+  if !json {
+    println!("t_ms,ax,ay,az,gx,gy,gz");
+  }
+  let mut n = 0;
+  while count.is_none_or(|c| n < c) {
+    let a = imu.get_accel_data().await.map_err(fail)?;
+    let g = imu.get_gyro_data().await.map_err(fail)?;
+    let t = start.elapsed().as_secs_f64() * 1e3;
+    if json {
+      println!(
+        "{{\"t_ms\":{t:.3},\"accel\":[{:.5},{:.5},{:.5}],\"gyro\":[{:.3},{:.3},{:.3}]}}",
+        a.x, a.y, a.z, g.x, g.y, g.z
+      );
+    } else {
+      println!("{t:.3},{:.5},{:.5},{:.5},{:.3},{:.3},{:.3}", a.x, a.y, a.z, g.x, g.y, g.z);
+    }
+    n += 1;
+    delay.delay_us((1e6 / odr) as u32).await;
+  }
+  Ok(())
+}
+
+fn parse_u16(s: &str) -> Result<u16, String> {
+  let v = match s.strip_prefix("0x") {
+    Some(hex) => u16::from_str_radix(hex, 16),
+    None => s.parse(),
+  };
+  v.map_err(|_| format!("invalid number `{s}`"))
+}
+
+fn parse_odr(s: &str) -> Result<OutputDataRate, String> {
+  let hz: f32 = s.parse().map_err(|_| format!("invalid ODR `{s}`"))?;
+  (0x01..=0x0E)
+    .filter_map(|v| OutputDataRate::try_from(v).ok())
+    .find(|odr| (odr.hz() - hz).abs() < 0.01 * odr.hz().max(1.0))
+    .ok_or_else(|| format!("unsupported ODR {s} Hz"))
+}
+
+fn parse_accel_mode(s: &str) -> Result<AccelPowerMode, String> {
+  match s {
+    "off" => Ok(AccelPowerMode::Disable),
+    "lowpower" => Ok(AccelPowerMode::LowPower),
+    "normal" => Ok(AccelPowerMode::Normal),
+    "highperf" => Ok(AccelPowerMode::HighPerf),
+    _ => Err(format!("unknown accel mode `{s}`")),
+  }
+}
+
+fn parse_gyro_mode(s: &str) -> Result<GyroPowerMode, String> {
+  match s {
+    "off" => Ok(GyroPowerMode::Disable),
+    "suspend" => Ok(GyroPowerMode::Suspend),
+    "lowpower" => Ok(GyroPowerMode::LowPower),
+    "normal" => Ok(GyroPowerMode::Normal),
+    "highperf" => Ok(GyroPowerMode::HighPerf),
+    _ => Err(format!("unknown gyro mode `{s}`")),
+  }
+}
+
+fn parse_accel_range(s: &str) -> Result<AccelRange, String> {
+  match s {
+    "2" => Ok(AccelRange::G2),
+    "4" => Ok(AccelRange::G4),
+    "8" => Ok(AccelRange::G8),
+    "16" => Ok(AccelRange::G16),
+    _ => Err(format!("unsupported accel range `{s}` g")),
+  }
+}
+
+fn parse_gyro_range(s: &str) -> Result<GyroRange, String> {
+  match s {
+    "125" => Ok(GyroRange::DPS125),
+    "250" => Ok(GyroRange::DPS250),
+    "500" => Ok(GyroRange::DPS500),
+    "1000" => Ok(GyroRange::DPS1000),
+    "2000" => Ok(GyroRange::DPS2000),
+    _ => Err(format!("unsupported gyro range `{s}` dps")),
+  }
+}
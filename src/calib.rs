@@ -34,12 +34,12 @@ where
       .await
   }
 
-  /// Alternate auto-config: write raw block (bytes).
+  /// Alternate auto-config: write raw block (bytes). See [`set_alt_auto_conf`](Self::set_alt_auto_conf) for the typed API.
   pub async fn set_alt_auto_config(&mut self, bytes: &[u8]) -> Result<(), Error<E>> {
     self.write_feature_bytes(super::FeatureAddr::AltAutoConfig, bytes).await
   }
 
-  /// Alternate auto-config: read raw block (bytes).
+  /// Alternate auto-config: read raw block (bytes). See [`get_alt_auto_conf`](Self::get_alt_auto_conf) for the typed API.
   pub async fn get_alt_auto_config(&mut self, out: &mut [u8]) -> Result<(), Error<E>> {
     self.read_feature_bytes(super::FeatureAddr::AltAutoConfig, out).await
   }
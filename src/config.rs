@@ -0,0 +1,371 @@
+//! Declarative whole-device configuration.
+//!
+//! Bringing the device up takes a reset followed by many `set_*` calls in an
+//! order the chip accepts. [`DeviceConfig`] collects the sensor, FIFO,
+//! interrupt and feature-engine settings in one value; [`DeviceConfig::apply`]
+//! writes them in the right order and [`DeviceConfig::read_back`] compares the
+//! device against the desired configuration.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::{accel::AccelPowerMode, config::DeviceConfig, interrupt::IntPin, tap::TapConfig};
+//! use bmi323::{FeatureSettings, Features};
+//!
+//! let mut cfg = DeviceConfig::default();
+//! cfg.accel.mode = AccelPowerMode::Normal;
+//! cfg.int1.enable = true;
+//! cfg.int_map.tap = IntPin::Int1;
+//! cfg.features = Some(FeatureSettings {
+//!   tap: Some(TapConfig::default()),
+//!   enabled: Features::TAP,
+//!   ..Default::default()
+//! });
+//!
+//! cfg.apply(&mut imu).await.unwrap();
+//! assert!(cfg.read_back(&mut imu).await.unwrap().is_empty());
+//! # }
+//! ```
+//!
+//! # Stored profiles
+//!
+//! [`DeviceConfig::to_bytes`] encodes a configuration into a fixed
+//! [`PROFILE_LEN`]-byte profile built from the register layouts, suitable for
+//! a flash page or a `const` array; [`DeviceConfig::from_bytes`] decodes it.
+//! With the `serde` feature the configuration types also implement
+//! `Serialize`/`Deserialize`, e.g. for provisioning from a JSON or TOML file.
+//!
+//! ```
+//! use bmi323::config::{DeviceConfig, PROFILE_LEN};
+//!
+//! const PROFILE: [u8; PROFILE_LEN] = [0; PROFILE_LEN];
+//! assert!(DeviceConfig::from_bytes(&PROFILE).is_err()); // no version byte
+//!
+//! let cfg = DeviceConfig::default();
+//! assert_eq!(DeviceConfig::from_bytes(&cfg.to_bytes()), Ok(cfg));
+//! ```
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::{
+  accel::AccelConfig,
+  fifo::FifoConfig,
+  gyro::GyroConfig,
+  interrupt::{IntConfig, IntMap, IoIntCtrl},
+  Bmi323, Error, FeatureSettings,
+};
+
+/// Size of an encoded [`DeviceConfig`] profile in bytes.
+pub const PROFILE_LEN: usize = 60;
+
+/// Profile format version, stored in the first byte.
+pub const PROFILE_VERSION: u8 = 1;
+
+const FLAG_INT_LATCH: u8 = 1 << 0;
+const FLAG_FEATURES: u8 = 1 << 1;
+
+/// Complete device configuration, applied by [`DeviceConfig::apply`].
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DeviceConfig {
+  pub accel: AccelConfig,
+  pub gyro: GyroConfig,
+  pub fifo: FifoConfig,
+  /// FIFO watermark in words (10 bits).
+  pub fifo_watermark: u16,
+  pub int_latch: bool,
+  pub int1: IntConfig,
+  pub int2: IntConfig,
+  pub int_map: IntMap,
+  /// Feature-engine settings (`None`: the feature engine stays disabled).
+  pub features: Option<FeatureSettings>,
+}
+
+impl DeviceConfig {
+  /// Reset the device and write the whole configuration.
+  ///
+  /// Order: reset and chip check, feature engine, feature configurations
+  /// and enable mask, accelerometer and gyroscope, FIFO, interrupt pins and
+  /// finally the interrupt map. The feature settings precede the sensors
+  /// because the axis remap is refused while a sensor is active. Nothing is
+  /// written if the sensor configurations fail validation.
+  pub async fn apply<I, D, W, E>(&self, imu: &mut Bmi323<I, D, W>) -> Result<(), Error<E>>
+  where
+    I: I2c<SevenBitAddress, Error = E>,
+    D: DelayNs,
+  {
+    self.accel.validate().map_err(Error::Config)?;
+    self.gyro.validate().map_err(Error::Config)?;
+
+    imu.init(self.features.is_some()).await?;
+    // The axis remap is refused while a sensor is active, so the feature
+    // settings go first.
+    if let Some(features) = self.features {
+      imu.apply_features(features).await?;
+    }
+    imu.set_accel_conf(self.accel).await?;
+    imu.set_gyro_conf(self.gyro).await?;
+    imu.set_fifo_config(self.fifo).await?;
+    imu.set_fifo_watermark(self.fifo_watermark).await?;
+    imu.set_int_config(self.int_latch, self.int1, self.int2).await?;
+    imu.set_int_map(self.int_map).await?;
+    Ok(())
+  }
+
+  /// Read the device configuration and report which parts differ from `self`.
+  ///
+  /// Feature configuration blocks are only read if they are set in
+  /// `self.features`.
+  pub async fn read_back<I, D, W, E>(&self, imu: &mut Bmi323<I, D, W>) -> Result<ConfigDiff, Error<E>>
+  where
+    I: I2c<SevenBitAddress, Error = E>,
+    D: DelayNs,
+  {
+    let (int1, int2) = imu.get_int_config().await?;
+    let mut diff = ConfigDiff {
+      accel: imu.get_accel_conf().await? != self.accel,
+      gyro: imu.get_gyro_conf().await? != self.gyro,
+      fifo: imu.get_fifo_config().await? != self.fifo,
+      fifo_watermark: imu.get_fifo_watermark().await? != self.fifo_watermark.min(0x3FF),
+      int_latch: imu.get_int_latch().await? != self.int_latch,
+      int_pins: (int1, int2) != (self.int1, self.int2),
+      int_map: imu.get_int_map().await? != self.int_map,
+      ..ConfigDiff::default()
+    };
+
+    let engine = imu.is_feature_engine_enabled().await?;
+    let Some(f) = self.features else {
+      diff.feature_engine = engine;
+      return Ok(diff);
+    };
+    if !engine {
+      diff.feature_engine = true;
+      return Ok(diff);
+    }
+    diff.enabled_features = imu.get_enabled_features().await? != f.enabled;
+    if let Some(v) = f.axis_remap {
+      diff.axis_remap = imu.get_axis_remap().await? != v;
+    }
+    if let Some(v) = f.any_motion {
+      diff.any_motion = imu.get_any_motion_conf().await? != v;
+    }
+    if let Some(v) = f.no_motion {
+      diff.no_motion = imu.get_no_motion_conf().await? != v;
+    }
+    if let Some(v) = f.flat {
+      diff.flat = imu.get_flat_conf().await? != v;
+    }
+    if let Some(v) = f.orientation {
+      diff.orientation = imu.get_orientation_conf().await? != v;
+    }
+    if let Some(v) = f.sig_motion {
+      diff.sig_motion = imu.get_sign_motion_conf().await? != v;
+    }
+    if let Some(v) = f.tap {
+      diff.tap = imu.get_tap_conf().await? != v;
+    }
+    if let Some(v) = f.tilt {
+      diff.tilt = imu.get_tilt_conf().await? != v;
+    }
+    if let Some(v) = f.step_watermark {
+      diff.step_watermark = imu.get_step_watermark().await? != v.min(0x3FF);
+    }
+    Ok(diff)
+  }
+}
+
+impl DeviceConfig {
+  /// Encode into a compact, fixed-size profile.
+  ///
+  /// Layout (little-endian, register encodings as on the device):
+  ///
+  /// | Offset | Size | Content |
+  /// |---|---|---|
+  /// | 0 | 1 | [`PROFILE_VERSION`] |
+  /// | 1 | 1 | flags: bit 0 `int_latch`, bit 1 `features` present |
+  /// | 2 | 6 | `ACC_CONF`, `GYR_CONF`, `FIFO_CONF` |
+  /// | 8 | 2 | FIFO watermark |
+  /// | 10 | 2 | `IO_INT_CTRL` (both pins) |
+  /// | 12 | 4 | `INT_MAP1`/`INT_MAP2` |
+  /// | 16 | 2 | feature blocks present: bit 0 axis remap … bit 8 step watermark |
+  /// | 18 | 2 | feature enable mask (`FEATURE_IO0`) |
+  /// | 20 | 40 | feature blocks in [`FeatureSettings`] field order (absent blocks are zero) |
+  pub fn to_bytes(&self) -> [u8; PROFILE_LEN] {
+    let mut w = Writer { buf: [0; PROFILE_LEN], pos: 2 };
+    w.buf[0] = PROFILE_VERSION;
+    if self.int_latch {
+      w.buf[1] |= FLAG_INT_LATCH;
+    }
+    w.put(self.accel);
+    w.put(self.gyro);
+    w.put(self.fifo);
+    w.put(self.fifo_watermark.to_le_bytes());
+    w.put(IoIntCtrl::from_pins(self.int1, self.int2));
+    w.put(self.int_map);
+
+    let f = self.features.unwrap_or_default();
+    if self.features.is_some() {
+      w.buf[1] |= FLAG_FEATURES;
+    }
+    let present = [
+      f.axis_remap.is_some(),
+      f.any_motion.is_some(),
+      f.no_motion.is_some(),
+      f.flat.is_some(),
+      f.orientation.is_some(),
+      f.sig_motion.is_some(),
+      f.tap.is_some(),
+      f.tilt.is_some(),
+      f.step_watermark.is_some(),
+    ];
+    let mask = present.iter().enumerate().fold(0u16, |m, (i, &p)| m | (p as u16) << i);
+    w.put(mask.to_le_bytes());
+    w.put(f.enabled);
+    w.put_opt(f.axis_remap);
+    w.put_opt(f.any_motion);
+    w.put_opt(f.no_motion);
+    w.put_opt(f.flat);
+    w.put_opt(f.orientation);
+    w.put_opt(f.sig_motion);
+    w.put_opt(f.tap);
+    w.put_opt(f.tilt);
+    w.put_opt(f.step_watermark.map(u16::to_le_bytes));
+    w.buf
+  }
+
+  /// Decode a profile written by [`to_bytes`](Self::to_bytes).
+  pub fn from_bytes(bytes: &[u8; PROFILE_LEN]) -> Result<Self, ProfileError> {
+    if bytes[0] != PROFILE_VERSION {
+      return Err(ProfileError::UnsupportedVersion(bytes[0]));
+    }
+    let flags = bytes[1];
+    let mut r = Reader { buf: bytes, pos: 2 };
+    let accel = r.get()?;
+    let gyro = r.get()?;
+    let fifo = r.get()?;
+    let fifo_watermark = u16::from_le_bytes(r.get()?);
+    let (int1, int2) = r.get::<2, IoIntCtrl>()?.pins();
+    let int_map = r.get()?;
+
+    let mask = u16::from_le_bytes(r.get()?);
+    let mut bit = 0;
+    let mut present = || {
+      bit += 1;
+      mask & (1 << (bit - 1)) != 0
+    };
+    let features = FeatureSettings {
+      enabled: r.get()?,
+      axis_remap: r.get_opt(present())?,
+      any_motion: r.get_opt(present())?,
+      no_motion: r.get_opt(present())?,
+      flat: r.get_opt(present())?,
+      orientation: r.get_opt(present())?,
+      sig_motion: r.get_opt(present())?,
+      tap: r.get_opt(present())?,
+      tilt: r.get_opt(present())?,
+      step_watermark: r.get_opt(present())?.map(u16::from_le_bytes),
+    };
+
+    Ok(Self {
+      accel,
+      gyro,
+      fifo,
+      fifo_watermark,
+      int_latch: flags & FLAG_INT_LATCH != 0,
+      int1,
+      int2,
+      int_map,
+      features: (flags & FLAG_FEATURES != 0).then_some(features),
+    })
+  }
+}
+
+/// Error decoding a stored profile, see [`DeviceConfig::from_bytes`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ProfileError {
+  /// The first byte is not [`PROFILE_VERSION`] (e.g. erased flash).
+  UnsupportedVersion(u8),
+  /// The field at this byte offset holds a reserved value.
+  InvalidField(usize),
+}
+
+struct Writer {
+  buf: [u8; PROFILE_LEN],
+  pos: usize,
+}
+
+impl Writer {
+  fn put<const N: usize, T: TryInto<[u8; N]>>(&mut self, v: T) {
+    // Encoding cannot fail: every enum field converts to its register code.
+    let b = v.try_into().unwrap_or([0; N]);
+    self.buf[self.pos..self.pos + N].copy_from_slice(&b);
+    self.pos += N;
+  }
+
+  fn put_opt<const N: usize, T: TryInto<[u8; N]>>(&mut self, v: Option<T>) {
+    match v {
+      Some(v) => self.put(v),
+      None => self.pos += N,
+    }
+  }
+}
+
+struct Reader<'a> {
+  buf: &'a [u8; PROFILE_LEN],
+  pos: usize,
+}
+
+impl Reader<'_> {
+  fn get<const N: usize, T: TryFrom<[u8; N]>>(&mut self) -> Result<T, ProfileError> {
+    let mut b = [0u8; N];
+    b.copy_from_slice(&self.buf[self.pos..self.pos + N]);
+    let v = T::try_from(b).map_err(|_| ProfileError::InvalidField(self.pos))?;
+    self.pos += N;
+    Ok(v)
+  }
+
+  fn get_opt<const N: usize, T: TryFrom<[u8; N]>>(&mut self, present: bool) -> Result<Option<T>, ProfileError> {
+    if present {
+      self.get().map(Some)
+    } else {
+      self.pos += N;
+      Ok(None)
+    }
+  }
+}
+
+/// Parts of a [`DeviceConfig`] that differ on the device (`true` = mismatch).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigDiff {
+  pub accel: bool,
+  pub gyro: bool,
+  pub fifo: bool,
+  pub fifo_watermark: bool,
+  pub int_latch: bool,
+  pub int_pins: bool,
+  pub int_map: bool,
+  /// The feature engine state does not match `features.is_some()`. The
+  /// feature blocks are not compared in that case.
+  pub feature_engine: bool,
+  pub enabled_features: bool,
+  pub axis_remap: bool,
+  pub any_motion: bool,
+  pub no_motion: bool,
+  pub flat: bool,
+  pub orientation: bool,
+  pub sig_motion: bool,
+  pub tap: bool,
+  pub tilt: bool,
+  pub step_watermark: bool,
+}
+
+impl ConfigDiff {
+  /// True if the device matches the desired configuration.
+  pub fn is_empty(&self) -> bool {
+    *self == Self::default()
+  }
+}
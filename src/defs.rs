@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 
+/// Register addresses of the BMI323 (primary register map).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
-pub(crate) enum Reg {
+pub enum Reg {
   ChipId = 0x00,
   Err = 0x01,
   Status = 0x02,
@@ -61,9 +64,146 @@ pub(crate) enum Reg {
   CfgRes = 0x7F,
 }
 
+impl Reg {
+  /// Every named register, in address order.
+  pub const ALL: [Reg; 57] = [
+    Reg::ChipId,
+    Reg::Err,
+    Reg::Status,
+    Reg::AccDataX,
+    Reg::GyrDataX,
+    Reg::TempData,
+    Reg::SensorTime0,
+    Reg::SatFlags,
+    Reg::IntStatusInt1,
+    Reg::IntStatusInt2,
+    Reg::FeatureIo0,
+    Reg::FeatureIo1,
+    Reg::FeatureIo2,
+    Reg::FeatureIo3,
+    Reg::FeatureIoStatus,
+    Reg::FifoFillLevel,
+    Reg::FifoData,
+    Reg::AccConf,
+    Reg::GyrConf,
+    Reg::AltAccConf,
+    Reg::AltGyrConf,
+    Reg::AltConf,
+    Reg::AltStatus,
+    Reg::FifoWatermark,
+    Reg::FifoConf,
+    Reg::FifoCtrl,
+    Reg::IoIntCtrl,
+    Reg::IntConf,
+    Reg::IntMap1,
+    Reg::FeatureCtrl,
+    Reg::FeatureDataAddr,
+    Reg::FeatureDataTx,
+    Reg::FeatureDataStatus,
+    Reg::FeatureEngineStatus,
+    Reg::FeatureEventExt,
+    Reg::IoPdnCtrl,
+    Reg::IoSpiIf,
+    Reg::IoPadStrength,
+    Reg::IoI2cIf,
+    Reg::IoOdrDeviation,
+    Reg::AccDpOffX,
+    Reg::AccDpDGainX,
+    Reg::AccDpOffY,
+    Reg::AccDpDGainY,
+    Reg::AccDpOffZ,
+    Reg::AccDpDGainZ,
+    Reg::GyrDpOffX,
+    Reg::GyrDpDGainX,
+    Reg::GyrDpOffY,
+    Reg::GyrDpDGainY,
+    Reg::GyrDpOffZ,
+    Reg::GyrDpDGainZ,
+    Reg::I3cTcSyncTph,
+    Reg::I3cTcSyncTu,
+    Reg::I3cTcSyncOdr,
+    Reg::Cmd,
+    Reg::CfgRes,
+  ];
+
+  /// Datasheet name of the register.
+  pub fn name(self) -> &'static str {
+    match self {
+      Reg::ChipId => "CHIP_ID",
+      Reg::Err => "ERR_REG",
+      Reg::Status => "STATUS",
+      Reg::AccDataX => "ACC_DATA_X",
+      Reg::GyrDataX => "GYR_DATA_X",
+      Reg::TempData => "TEMP_DATA",
+      Reg::SensorTime0 => "SENSOR_TIME_0",
+      Reg::SatFlags => "SAT_FLAGS",
+      Reg::IntStatusInt1 => "INT_STATUS_INT1",
+      Reg::IntStatusInt2 => "INT_STATUS_INT2",
+      Reg::FeatureIo0 => "FEATURE_IO0",
+      Reg::FeatureIo1 => "FEATURE_IO1",
+      Reg::FeatureIo2 => "FEATURE_IO2",
+      Reg::FeatureIo3 => "FEATURE_IO3",
+      Reg::FeatureIoStatus => "FEATURE_IO_STATUS",
+      Reg::FifoFillLevel => "FIFO_FILL_LEVEL",
+      Reg::FifoData => "FIFO_DATA",
+      Reg::AccConf => "ACC_CONF",
+      Reg::GyrConf => "GYR_CONF",
+      Reg::AltAccConf => "ALT_ACC_CONF",
+      Reg::AltGyrConf => "ALT_GYR_CONF",
+      Reg::AltConf => "ALT_CONF",
+      Reg::AltStatus => "ALT_STATUS",
+      Reg::FifoWatermark => "FIFO_WATERMARK",
+      Reg::FifoConf => "FIFO_CONF",
+      Reg::FifoCtrl => "FIFO_CTRL",
+      Reg::IoIntCtrl => "IO_INT_CTRL",
+      Reg::IntConf => "INT_CONF",
+      Reg::IntMap1 => "INT_MAP1",
+      Reg::FeatureCtrl => "FEATURE_CTRL",
+      Reg::FeatureDataAddr => "FEATURE_DATA_ADDR",
+      Reg::FeatureDataTx => "FEATURE_DATA_TX",
+      Reg::FeatureDataStatus => "FEATURE_DATA_STATUS",
+      Reg::FeatureEngineStatus => "FEATURE_ENGINE_STATUS",
+      Reg::FeatureEventExt => "FEATURE_EVENT_EXT",
+      Reg::IoPdnCtrl => "IO_PDN_CTRL",
+      Reg::IoSpiIf => "IO_SPI_IF",
+      Reg::IoPadStrength => "IO_PAD_STRENGTH",
+      Reg::IoI2cIf => "IO_I2C_IF",
+      Reg::IoOdrDeviation => "IO_ODR_DEVIATION",
+      Reg::AccDpOffX => "ACC_DP_OFF_X",
+      Reg::AccDpDGainX => "ACC_DP_DGAIN_X",
+      Reg::AccDpOffY => "ACC_DP_OFF_Y",
+      Reg::AccDpDGainY => "ACC_DP_DGAIN_Y",
+      Reg::AccDpOffZ => "ACC_DP_OFF_Z",
+      Reg::AccDpDGainZ => "ACC_DP_DGAIN_Z",
+      Reg::GyrDpOffX => "GYR_DP_OFF_X",
+      Reg::GyrDpDGainX => "GYR_DP_DGAIN_X",
+      Reg::GyrDpOffY => "GYR_DP_OFF_Y",
+      Reg::GyrDpDGainY => "GYR_DP_DGAIN_Y",
+      Reg::GyrDpOffZ => "GYR_DP_OFF_Z",
+      Reg::GyrDpDGainZ => "GYR_DP_DGAIN_Z",
+      Reg::I3cTcSyncTph => "I3C_TC_SYNC_TPH",
+      Reg::I3cTcSyncTu => "I3C_TC_SYNC_TU",
+      Reg::I3cTcSyncOdr => "I3C_TC_SYNC_ODR",
+      Reg::Cmd => "CMD",
+      Reg::CfgRes => "CFG_RES",
+    }
+  }
+
+  /// `FIFO_DATA` and `FEATURE_DATA_TX` are ports: reading them consumes data.
+  pub fn is_data_port(self) -> bool {
+    matches!(self, Reg::FifoData | Reg::FeatureDataTx)
+  }
+
+  /// `CMD` cannot be read back.
+  pub fn is_write_only(self) -> bool {
+    matches!(self, Reg::Cmd)
+  }
+}
+
 #[repr(u16)]
 pub(crate) enum Command {
   SelfTestTrigger = 0x0100,
+  I3cTcSyncUpdate = 0x0201,
   AxisMapUpdate = 0x0300,
   SoftReset = 0xDEAF,
 }
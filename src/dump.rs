@@ -0,0 +1,336 @@
+//! Whole-device register and feature-memory dump.
+//!
+//! [`dump_registers`](crate::Bmi323::dump_registers) reads every named
+//! register and each feature-memory block into a [`RegisterDump`]. Its
+//! `Debug` (and `defmt::Format`) output shows the decoded configuration, so a
+//! single log line captures the device state for a bug report.
+//! [`restore_registers`](crate::Bmi323::restore_registers) writes the
+//! configuration part of a dump back.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! let dump = imu.dump_registers().await.unwrap();
+//! // log it, e.g. `defmt::info!("{}", dump)`
+//! imu.restore_registers(&dump).await.unwrap();
+//! # }
+//! ```
+
+use core::fmt;
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::{
+  accel::AccelConfig,
+  alt::{AltAutoConfig, AltEnable, AltStatus},
+  any_no_motion::AnyNoMotionConfig,
+  axis_remap::AxisRemap,
+  defs::Reg,
+  fifo::FifoConfig,
+  flat::FlatConfig,
+  gyro::GyroConfig,
+  health::ErrorRegister,
+  i3c::TcSyncConfig,
+  interrupt::{FeatureEventExt, IntConfReg, IntConfig, IntMap, IntStatus, IoIntCtrl},
+  io::{IoI2cIf, IoOdrDeviation, IoPadStrength, IoPdnCtrl, IoSpiIf},
+  offset::{AccelOffsetGain, GyroOffsetGain},
+  orientation::OrientationConfig,
+  recovery::ConfigSnapshot,
+  sig_motion::SigMotionConfig,
+  tap::TapConfig,
+  tilt::TiltConfig,
+  Bmi323, Error, FeatureAddr, FeatureDataStatus, FeatureEngineStatus, FeatureIo1, Features, SaturationFlags,
+};
+
+const STEP_WATERMARK_MASK: u16 = 0x3FF;
+const FIFO_WATERMARK_MASK: u16 = 0x3FF;
+
+impl<I, D, W, E> Bmi323<I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Read every named register and feature-memory block.
+  ///
+  /// The data ports (`FIFO_DATA`, `FEATURE_DATA_TX`) and `CMD` are skipped,
+  /// as is feature memory while `FEATURE_CTRL.engine_en` is clear.
+  /// Clear-on-read registers (`INT_STATUS_INT1`/`INT2`, `FEATURE_EVENT_EXT`)
+  /// are cleared by the dump, as with their regular getters.
+  pub async fn dump_registers(&mut self) -> Result<RegisterDump, Error<E>> {
+    let mut accel_data = [0u8; 6];
+    self.read_bytes(Reg::AccDataX, &mut accel_data).await?;
+    let mut gyro_data = [0u8; 6];
+    self.read_bytes(Reg::GyrDataX, &mut gyro_data).await?;
+
+    // Feature memory is only reachable with the engine enabled; reads
+    // through FEATURE_DATA_TX would otherwise time out.
+    let feature_ctrl = self.read_u16(Reg::FeatureCtrl).await?;
+    let engine = feature_ctrl & 1 != 0;
+
+    Ok(RegisterDump {
+      chip_id: self.read_u16(Reg::ChipId).await? as u8,
+      err: self.dump_reg(Reg::Err).await?,
+      status: self.read_u16(Reg::Status).await?,
+      accel_data: words(accel_data).map(|w| w as i16),
+      gyro_data: words(gyro_data).map(|w| w as i16),
+      temp_data: self.read_u16(Reg::TempData).await? as i16,
+      sensor_time: self.get_sensor_time().await?,
+      sat_flags: self.dump_reg(Reg::SatFlags).await?,
+      int_status_int1: self.dump_reg(Reg::IntStatusInt1).await?,
+      int_status_int2: self.dump_reg(Reg::IntStatusInt2).await?,
+      features: self.dump_reg(Reg::FeatureIo0).await?,
+      feature_io1: self.dump_reg(Reg::FeatureIo1).await?,
+      feature_io2: self.read_u16(Reg::FeatureIo2).await?,
+      feature_io3: self.read_u16(Reg::FeatureIo3).await?,
+      feature_io_status: self.read_u16(Reg::FeatureIoStatus).await?,
+      fifo_fill_level: self.read_u16(Reg::FifoFillLevel).await?,
+      accel: self.dump_reg(Reg::AccConf).await?,
+      gyro: self.dump_reg(Reg::GyrConf).await?,
+      alt_accel: self.dump_reg(Reg::AltAccConf).await?,
+      alt_gyro: self.dump_reg(Reg::AltGyrConf).await?,
+      alt_enable: self.dump_reg(Reg::AltConf).await?,
+      alt_status: self.dump_reg(Reg::AltStatus).await?,
+      fifo_watermark: self.read_u16(Reg::FifoWatermark).await?,
+      fifo: self.dump_reg(Reg::FifoConf).await?,
+      fifo_ctrl: self.read_u16(Reg::FifoCtrl).await?,
+      int_pins: self
+        .dump_reg::<2, IoIntCtrl>(Reg::IoIntCtrl)
+        .await?
+        .map(IoIntCtrl::pins),
+      int_latch: self.dump_reg::<2, IntConfReg>(Reg::IntConf).await?.map(|r| r.latch),
+      int_map: self.dump_reg(Reg::IntMap1).await?,
+      feature_ctrl,
+      feature_data_addr: self.read_u16(Reg::FeatureDataAddr).await?,
+      feature_data_status: self.dump_reg(Reg::FeatureDataStatus).await?,
+      feature_engine_status: self.dump_reg(Reg::FeatureEngineStatus).await?,
+      feature_event_ext: self.dump_reg(Reg::FeatureEventExt).await?,
+      pdn_ctrl: self.dump_reg(Reg::IoPdnCtrl).await?,
+      spi_if: self.dump_reg(Reg::IoSpiIf).await?,
+      pad_strength: self.dump_reg(Reg::IoPadStrength).await?,
+      i2c_if: self.dump_reg(Reg::IoI2cIf).await?,
+      odr_deviation: self.dump_reg(Reg::IoOdrDeviation).await?,
+      accel_offset: self.dump_reg(Reg::AccDpOffX).await?,
+      gyro_offset: self.dump_reg(Reg::GyrDpOffX).await?,
+      i3c_tc_sync: self.get_i3c_tc_sync_conf().await?,
+      cfg_res: self.read_u16(Reg::CfgRes).await?,
+
+      axis_remap: self.dump_feature(engine, FeatureAddr::AxisRemap).await?,
+      any_motion: self.dump_feature(engine, FeatureAddr::AnyMotion).await?,
+      no_motion: self.dump_feature(engine, FeatureAddr::NoMotion).await?,
+      flat: self.dump_feature(engine, FeatureAddr::Flat).await?,
+      sig_motion: self.dump_feature(engine, FeatureAddr::SigMotion).await?,
+      step_watermark: self
+        .dump_feature_word(engine, FeatureAddr::StepCnt)
+        .await?
+        .map(|w| w & STEP_WATERMARK_MASK),
+      orientation: self.dump_feature(engine, FeatureAddr::Orient).await?,
+      tap: self.dump_feature(engine, FeatureAddr::Tap).await?,
+      tilt: self.dump_feature(engine, FeatureAddr::Tilt).await?,
+      alt_auto: self.dump_feature(engine, FeatureAddr::AltAutoConfig).await?,
+      st_result: self.dump_feature_word(engine, FeatureAddr::StResult).await?,
+      st_select: self.dump_feature_word(engine, FeatureAddr::StSelect).await?,
+      gyro_sc_select: self.dump_feature_word(engine, FeatureAddr::GyroScSelect).await?,
+      gyro_sc_st_conf: self.dump_feature_word(engine, FeatureAddr::GyroScStConf).await?,
+    })
+  }
+
+  /// Soft-reset the device and write the configuration held in `dump` back.
+  ///
+  /// The dump is turned into a [`ConfigSnapshot`] (see
+  /// [`RegisterDump::to_snapshot`]) that replaces the driver's own and is
+  /// replayed by [`recover`](Self::recover). Status, data and read-only
+  /// registers are not written, nor are fields that did not decode.
+  pub async fn restore_registers(&mut self, dump: &RegisterDump) -> Result<(), Error<E>> {
+    self.snapshot = dump.to_snapshot();
+    self.recover().await
+  }
+
+  async fn dump_reg<const N: usize, T: TryFrom<[u8; N]>>(&mut self, reg: Reg) -> Result<Decoded<T, N>, Error<E>> {
+    let mut raw = [0u8; N];
+    self.read_bytes(reg, &mut raw).await?;
+    Ok(Decoded::new(raw))
+  }
+
+  async fn dump_feature<const N: usize, T: TryFrom<[u8; N]>>(
+    &mut self,
+    engine: bool,
+    addr: FeatureAddr,
+  ) -> Result<Decoded<T, N>, Error<E>> {
+    let mut raw = [0u8; N];
+    if !engine {
+      return Ok(Decoded { raw, value: None });
+    }
+    self.read_feature_bytes(addr, &mut raw).await?;
+    Ok(Decoded::new(raw))
+  }
+
+  async fn dump_feature_word(&mut self, engine: bool, addr: FeatureAddr) -> Result<Option<u16>, Error<E>> {
+    if !engine {
+      return Ok(None);
+    }
+    let mut raw = [0u8; 2];
+    self.read_feature_bytes(addr, &mut raw).await?;
+    Ok(Some(u16::from_le_bytes(raw)))
+  }
+}
+
+fn words(b: [u8; 6]) -> [u16; 3] {
+  [
+    u16::from_le_bytes([b[0], b[1]]),
+    u16::from_le_bytes([b[2], b[3]]),
+    u16::from_le_bytes([b[4], b[5]]),
+  ]
+}
+
+/// Raw register bytes with their decoded value (`None` if they do not decode,
+/// e.g. a reserved ODR code).
+///
+/// Formats as the decoded value, or as the raw bytes when decoding failed.
+#[derive(Clone, Copy, PartialEq)]
+pub struct Decoded<T, const N: usize> {
+  pub raw: [u8; N],
+  pub value: Option<T>,
+}
+
+impl<T: TryFrom<[u8; N]>, const N: usize> Decoded<T, N> {
+  pub fn new(raw: [u8; N]) -> Self {
+    Self { raw, value: T::try_from(raw).ok() }
+  }
+}
+
+impl<T, const N: usize> Decoded<T, N> {
+  fn map<U>(self, f: impl FnOnce(T) -> U) -> Decoded<U, N> {
+    Decoded { raw: self.raw, value: self.value.map(f) }
+  }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for Decoded<T, N> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.value {
+      Some(v) => v.fmt(f),
+      None => write!(f, "Undecoded({:02x?})", self.raw),
+    }
+  }
+}
+
+#[cfg(feature = "defmt")]
+impl<T: defmt::Format, const N: usize> defmt::Format for Decoded<T, N> {
+  fn format(&self, f: defmt::Formatter) {
+    match &self.value {
+      Some(v) => defmt::write!(f, "{}", v),
+      None => defmt::write!(f, "Undecoded({=[u8]:02x})", self.raw[..]),
+    }
+  }
+}
+
+/// Snapshot of all registers and feature-memory blocks, see [`Bmi323::dump_registers`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterDump {
+  pub chip_id: u8,
+  pub err: Decoded<ErrorRegister, 2>,
+  pub status: u16,
+  pub accel_data: [i16; 3],
+  pub gyro_data: [i16; 3],
+  pub temp_data: i16,
+  pub sensor_time: u32,
+  pub sat_flags: Decoded<SaturationFlags, 2>,
+  pub int_status_int1: Decoded<IntStatus, 2>,
+  pub int_status_int2: Decoded<IntStatus, 2>,
+  pub features: Decoded<Features, 2>,
+  pub feature_io1: Decoded<FeatureIo1, 2>,
+  pub feature_io2: u16,
+  pub feature_io3: u16,
+  pub feature_io_status: u16,
+  pub fifo_fill_level: u16,
+  pub accel: Decoded<AccelConfig, 2>,
+  pub gyro: Decoded<GyroConfig, 2>,
+  pub alt_accel: Decoded<AccelConfig, 2>,
+  pub alt_gyro: Decoded<GyroConfig, 2>,
+  pub alt_enable: Decoded<AltEnable, 2>,
+  pub alt_status: Decoded<AltStatus, 2>,
+  pub fifo_watermark: u16,
+  pub fifo: Decoded<FifoConfig, 2>,
+  pub fifo_ctrl: u16,
+  pub int_pins: Decoded<(IntConfig, IntConfig), 2>,
+  pub int_latch: Decoded<bool, 2>,
+  pub int_map: Decoded<IntMap, 4>,
+  pub feature_ctrl: u16,
+  pub feature_data_addr: u16,
+  pub feature_data_status: Decoded<FeatureDataStatus, 2>,
+  pub feature_engine_status: Decoded<FeatureEngineStatus, 2>,
+  pub feature_event_ext: Decoded<FeatureEventExt, 2>,
+  pub pdn_ctrl: Decoded<IoPdnCtrl, 2>,
+  pub spi_if: Decoded<IoSpiIf, 2>,
+  pub pad_strength: Decoded<IoPadStrength, 2>,
+  pub i2c_if: Decoded<IoI2cIf, 2>,
+  pub odr_deviation: Decoded<IoOdrDeviation, 2>,
+  pub accel_offset: Decoded<AccelOffsetGain, 12>,
+  pub gyro_offset: Decoded<GyroOffsetGain, 12>,
+  pub i3c_tc_sync: TcSyncConfig,
+  pub cfg_res: u16,
+
+  // Feature memory; not read (`None`) if the feature engine was disabled.
+  pub axis_remap: Decoded<AxisRemap, 2>,
+  pub any_motion: Decoded<AnyNoMotionConfig, 6>,
+  pub no_motion: Decoded<AnyNoMotionConfig, 6>,
+  pub flat: Decoded<FlatConfig, 4>,
+  pub sig_motion: Decoded<SigMotionConfig, 6>,
+  pub step_watermark: Option<u16>,
+  pub orientation: Decoded<OrientationConfig, 4>,
+  pub tap: Decoded<TapConfig, 6>,
+  pub tilt: Decoded<TiltConfig, 4>,
+  pub alt_auto: Decoded<AltAutoConfig, 2>,
+  pub st_result: Option<u16>,
+  pub st_select: Option<u16>,
+  pub gyro_sc_select: Option<u16>,
+  pub gyro_sc_st_conf: Option<u16>,
+}
+
+impl RegisterDump {
+  /// `FEATURE_CTRL.engine_en` at the time of the dump.
+  pub fn feature_engine_enabled(&self) -> bool {
+    self.feature_ctrl & 1 != 0
+  }
+
+  /// The writable configuration in this dump as a [`ConfigSnapshot`].
+  ///
+  /// Feature-engine settings are only included if the engine was enabled.
+  /// The I3C TC-sync time base is only included if it was programmed.
+  pub fn to_snapshot(&self) -> ConfigSnapshot {
+    let engine = self.feature_engine_enabled();
+    ConfigSnapshot {
+      feature_engine: engine,
+      i2c_if: self.i2c_if.value,
+      spi_if: self.spi_if.value,
+      pad_strength: self.pad_strength.value,
+      odr_deviation: self.odr_deviation.value,
+      pdn_ctrl: self.pdn_ctrl.value,
+      accel_offset: self.accel_offset.value,
+      gyro_offset: self.gyro_offset.value,
+      accel: self.accel.value,
+      gyro: self.gyro.value,
+      alt_accel: self.alt_accel.value,
+      alt_gyro: self.alt_gyro.value,
+      alt_enable: self.alt_enable.value,
+      alt_auto: self.alt_auto.value.filter(|_| engine),
+      fifo: self.fifo.value,
+      fifo_watermark: Some(self.fifo_watermark & FIFO_WATERMARK_MASK),
+      int_pins: self.int_pins.value,
+      int_latch: self.int_latch.value,
+      int_map: self.int_map.value,
+      features: self.features.value.filter(|_| engine),
+      axis_remap: self.axis_remap.value.filter(|_| engine),
+      any_motion: self.any_motion.value.filter(|_| engine),
+      no_motion: self.no_motion.value.filter(|_| engine),
+      flat: self.flat.value.filter(|_| engine),
+      orientation: self.orientation.value.filter(|_| engine),
+      sig_motion: self.sig_motion.value.filter(|_| engine),
+      tap: self.tap.value.filter(|_| engine),
+      tilt: self.tilt.value.filter(|_| engine),
+      step_watermark: self.step_watermark.filter(|_| engine),
+      i3c_tc_sync: Some(self.i3c_tc_sync).filter(|c| engine && *c != TcSyncConfig::new(0, 0, 0)),
+    }
+  }
+}
@@ -0,0 +1,135 @@
+//! Driver error types.
+
+use core::{convert::Infallible, fmt};
+
+use crate::{AverageNum, FeatureAddr, FeatureIoError, OutputDataRate, Reg, Sensor};
+
+/// Driver error type.
+///
+/// This error type wraps the underlying I2C error (`E`) and, for the event
+/// API, the interrupt pin error (`P`), and adds BMI323-specific error
+/// conditions. Methods that never touch a pin use the default `P = Infallible`.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E, P = Infallible> {
+  /// I2C communication error
+  I2c(E),
+  /// Interrupt pin (event source) error
+  Pin(P),
+  /// Chip ID mismatch (expected 0x43)
+  InvalidChipId(u8),
+  /// Invalid mode or configuration
+  InvalidMode,
+  /// Initialization failed (e.g., feature engine reported a wrong configuration string)
+  Init,
+  /// The device did not reach the expected state in time
+  Timeout(Operation),
+  /// Feature memory access outside the valid range (`FEATURE_DATA_STATUS.out_of_bound_err`)
+  FeatureOutOfBounds,
+  /// Register contents could not be converted to/from the typed value
+  InvalidRegisterValue(Reg),
+  /// Feature block contents could not be converted to/from the typed value
+  InvalidFeatureValue(FeatureAddr),
+  /// Buffer length not supported by the operation (e.g. odd byte count for 16-bit feature words)
+  InvalidLength(usize),
+  /// Configuration rejected by validation or by the device
+  Config(ConfigError),
+}
+
+/// Operation that timed out, carried by [`Error::Timeout`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Operation {
+  /// Waiting for `STATUS.drdy_*` after configuring a sensor
+  DataReady(Sensor),
+  /// Waiting for `FEATURE_IO1.error_status == Activated`
+  FeatureEngineActivation,
+  /// Waiting for `FEATURE_DATA_STATUS.data_tx_ready`
+  FeatureDataReady,
+  /// Waiting for `FEATURE_IO1.axis_map_complete`
+  AxisMapUpdate,
+}
+
+/// Reason a configuration was rejected, carried by [`Error::Config`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ConfigError {
+  /// The feature engine refused the request (`FEATURE_IO1.error_status`).
+  Rejected(FeatureIoError),
+  /// ODR above what the selected power mode supports.
+  OdrTooHigh { sensor: Sensor, max: OutputDataRate },
+  /// ODR below what the selected power mode supports.
+  OdrTooLow { sensor: Sensor, min: OutputDataRate },
+  /// The device flagged the written configuration as invalid (`ERR_REG.acc_conf_err`/`gyr_conf_err`).
+  SensorConfRejected(Sensor),
+  /// Averaging window does not fit the low-power duty cycle at this ODR.
+  AveragingTooHigh {
+    sensor: Sensor,
+    odr: OutputDataRate,
+    avg: AverageNum,
+  },
+  /// The matrix or axis directions do not describe a proper rotation
+  /// (orthonormal with determinant +1).
+  NotRotation,
+  /// The rotation is not a signed axis permutation, so the on-chip axis remap
+  /// cannot express it (see [`Rotation`](crate::axis_remap::Rotation) for the software fallback).
+  NotAxisAligned,
+}
+
+impl<E> Error<E> {
+  /// Re-type an error raised outside the event API (which never carries a pin
+  /// error) so it can be returned alongside event errors.
+  pub fn widen<P>(self) -> Error<E, P> {
+    match self {
+      Error::I2c(e) => Error::I2c(e),
+      Error::Pin(never) => match never {},
+      Error::InvalidChipId(id) => Error::InvalidChipId(id),
+      Error::InvalidMode => Error::InvalidMode,
+      Error::Init => Error::Init,
+      Error::Timeout(op) => Error::Timeout(op),
+      Error::FeatureOutOfBounds => Error::FeatureOutOfBounds,
+      Error::InvalidRegisterValue(reg) => Error::InvalidRegisterValue(reg),
+      Error::InvalidFeatureValue(addr) => Error::InvalidFeatureValue(addr),
+      Error::InvalidLength(len) => Error::InvalidLength(len),
+      Error::Config(e) => Error::Config(e),
+    }
+  }
+}
+
+impl<E: fmt::Debug, P: fmt::Debug> fmt::Display for Error<E, P> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Error::I2c(e) => write!(f, "I2C error: {e:?}"),
+      Error::Pin(e) => write!(f, "interrupt pin error: {e:?}"),
+      Error::InvalidChipId(id) => write!(f, "unexpected chip ID {id:#04x} (expected 0x43)"),
+      Error::InvalidMode => write!(f, "invalid mode"),
+      Error::Init => write!(f, "feature engine initialization failed"),
+      Error::Timeout(op) => write!(f, "timed out waiting for {op:?}"),
+      Error::FeatureOutOfBounds => write!(f, "feature memory access out of bounds"),
+      Error::InvalidRegisterValue(reg) => write!(f, "invalid value in register {reg:?}"),
+      Error::InvalidFeatureValue(addr) => write!(f, "invalid value in feature block {addr:?}"),
+      Error::InvalidLength(len) => write!(f, "unsupported buffer length {len}"),
+      Error::Config(e) => write!(f, "configuration rejected: {e:?}"),
+    }
+  }
+}
+
+impl<E: fmt::Debug, P: fmt::Debug> core::error::Error for Error<E, P> {}
+
+impl fmt::Display for ConfigError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      ConfigError::Rejected(status) => write!(f, "feature engine rejected the request: {status:?}"),
+      ConfigError::OdrTooHigh { sensor, max } => write!(f, "{sensor:?} ODR above {max:?} for this power mode"),
+      ConfigError::OdrTooLow { sensor, min } => write!(f, "{sensor:?} ODR below {min:?} for this power mode"),
+      ConfigError::SensorConfRejected(sensor) => write!(f, "device rejected the {sensor:?} configuration"),
+      ConfigError::AveragingTooHigh { sensor, odr, avg } => {
+        write!(f, "{sensor:?} averaging {avg:?} too high for low-power mode at {odr:?}")
+      }
+      ConfigError::NotRotation => write!(f, "not a proper rotation matrix"),
+      ConfigError::NotAxisAligned => write!(f, "rotation is not axis-aligned"),
+    }
+  }
+}
+
+impl core::error::Error for ConfigError {}
@@ -5,11 +5,18 @@ use super::{interrupt::*, Bmi323, Error};
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Event {
-  Tap(u8),
+  /// A tap gesture. The engine does not report which axis was tapped; it only
+  /// evaluates the axis selected by [`TapConfig::axis`](crate::tap::TapConfig::axis).
+  Tap {
+    kind: TapKind,
+  },
   NoMotion,
   AnyMotion,
   Flat,
-  Orientation { pl: OrientationPl, face: Face },
+  Orientation {
+    pl: OrientationPl,
+    face: Face,
+  },
   StepDetector,
   StepCounter,
   SigMotion,
@@ -22,32 +29,310 @@ pub enum Event {
   ErrStatus,
 }
 
+impl Event {
+  /// Payload-free discriminant of this event, used for filtering.
+  pub const fn kind(&self) -> EventKind {
+    match self {
+      Event::Tap { .. } => EventKind::Tap,
+      Event::NoMotion => EventKind::NoMotion,
+      Event::AnyMotion => EventKind::AnyMotion,
+      Event::Flat => EventKind::Flat,
+      Event::Orientation { .. } => EventKind::Orientation,
+      Event::StepDetector => EventKind::StepDetector,
+      Event::StepCounter => EventKind::StepCounter,
+      Event::SigMotion => EventKind::SigMotion,
+      Event::Tilt => EventKind::Tilt,
+      Event::TempDataReady => EventKind::TempDataReady,
+      Event::GyrDataReady => EventKind::GyrDataReady,
+      Event::AccelDataReady => EventKind::AccelDataReady,
+      Event::FifoWatermark => EventKind::FifoWatermark,
+      Event::FifoFull => EventKind::FifoFull,
+      Event::ErrStatus => EventKind::ErrStatus,
+    }
+  }
+}
+
+/// Event discriminants (one bit each in [`EventFilter`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EventKind {
+  Tap = 0,
+  NoMotion = 1,
+  AnyMotion = 2,
+  Flat = 3,
+  Orientation = 4,
+  StepDetector = 5,
+  StepCounter = 6,
+  SigMotion = 7,
+  Tilt = 8,
+  TempDataReady = 9,
+  GyrDataReady = 10,
+  AccelDataReady = 11,
+  FifoWatermark = 12,
+  FifoFull = 13,
+  ErrStatus = 14,
+}
+
+/// Bitmask selecting which [`EventKind`]s an application is interested in.
+///
+/// ```no_run
+/// use bmi323::{EventFilter, EventKind};
+///
+/// let filter = EventFilter::NONE.with(EventKind::Tap).with(EventKind::Orientation);
+/// assert!(filter.contains(EventKind::Tap));
+/// assert!(!filter.contains(EventKind::AnyMotion));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct EventFilter(u16);
+
+impl EventFilter {
+  /// Matches nothing.
+  pub const NONE: Self = Self(0);
+  /// Matches every event.
+  pub const ALL: Self = Self((1 << 15) - 1);
+
+  /// Filter matching a single kind.
+  pub const fn only(kind: EventKind) -> Self {
+    Self(1 << kind as u16)
+  }
+
+  /// Add `kind` to the filter.
+  pub const fn with(self, kind: EventKind) -> Self {
+    Self(self.0 | 1 << kind as u16)
+  }
+
+  /// Remove `kind` from the filter.
+  pub const fn without(self, kind: EventKind) -> Self {
+    Self(self.0 & !(1 << kind as u16))
+  }
+
+  pub const fn contains(self, kind: EventKind) -> bool {
+    self.0 & (1 << kind as u16) != 0
+  }
+
+  pub const fn matches(self, event: &Event) -> bool {
+    self.contains(event.kind())
+  }
+}
+
+impl Default for EventFilter {
+  fn default() -> Self {
+    Self::ALL
+  }
+}
+
+impl From<EventKind> for EventFilter {
+  fn from(kind: EventKind) -> Self {
+    Self::only(kind)
+  }
+}
+
+impl core::ops::BitOr for EventFilter {
+  type Output = Self;
+  fn bitor(self, rhs: Self) -> Self {
+    Self(self.0 | rhs.0)
+  }
+}
+
+impl core::ops::BitOr<EventKind> for EventFilter {
+  type Output = Self;
+  fn bitor(self, rhs: EventKind) -> Self {
+    self.with(rhs)
+  }
+}
+
+/// Per-event callbacks driven by [`Bmi323::dispatch`].
+///
+/// Every callback defaults to a no-op, so implement only the ones you need and
+/// narrow [`filter`](Self::filter) to match; events outside the filter are
+/// consumed without invoking the handler.
+///
+/// ```no_run
+/// use bmi323::interrupt::{Face, OrientationPl, TapKind};
+/// use bmi323::{EventFilter, EventHandler, EventKind};
+///
+/// struct Ui;
+///
+/// impl EventHandler for Ui {
+///   fn filter(&self) -> EventFilter {
+///     EventFilter::only(EventKind::Tap) | EventKind::Orientation
+///   }
+///
+///   async fn on_tap(&mut self, kind: TapKind) { /* ... */ }
+///   async fn on_orientation(&mut self, pl: OrientationPl, face: Face) { /* ... */ }
+/// }
+/// ```
+#[allow(async_fn_in_trait, unused_variables)]
+pub trait EventHandler {
+  /// Kinds this handler wants to receive. Defaults to all events.
+  fn filter(&self) -> EventFilter {
+    EventFilter::ALL
+  }
+
+  async fn on_tap(&mut self, kind: TapKind) {}
+  async fn on_no_motion(&mut self) {}
+  async fn on_any_motion(&mut self) {}
+  async fn on_flat(&mut self) {}
+  async fn on_orientation(&mut self, pl: OrientationPl, face: Face) {}
+  async fn on_step_detector(&mut self) {}
+  async fn on_step_counter(&mut self) {}
+  async fn on_sig_motion(&mut self) {}
+  async fn on_tilt(&mut self) {}
+  async fn on_temp_data_ready(&mut self) {}
+  async fn on_gyr_data_ready(&mut self) {}
+  async fn on_accel_data_ready(&mut self) {}
+  async fn on_fifo_watermark(&mut self) {}
+  async fn on_fifo_full(&mut self) {}
+  async fn on_err_status(&mut self) {}
+}
+
+/// Something that tells the driver when to sample the interrupt status registers.
+///
+/// Implemented for every [`digital::Wait`] pin (wired to INT1) and for
+/// [`Polling`], which needs no interrupt line at all.
+#[allow(async_fn_in_trait)]
+pub trait EventSource {
+  type Error;
+
+  /// Return once interrupt status should be read.
+  async fn wait<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Self::Error>;
+
+  /// Whether `INT_STATUS_INT2` is read in addition to `INT_STATUS_INT1`.
+  fn reads_int2(&self) -> bool {
+    false
+  }
+}
+
+impl<P: digital::Wait> EventSource for P {
+  type Error = P::Error;
+
+  async fn wait<D: DelayNs>(&mut self, _delay: &mut D) -> Result<(), Self::Error> {
+    self.wait_for_any_edge().await
+  }
+}
+
+/// Pin-less event source for boards without a wired interrupt line.
+///
+/// Every `interval` the driver reads both `INT_STATUS_INT1` and
+/// `INT_STATUS_INT2` and decodes them into the same [`Event`] stream a pin
+/// would produce. Map the interesting sources to either pin in
+/// [`IntMap`]; the pins themselves do not need to be enabled.
+///
+/// Status registers are clear-on-read, so an event mapped to both INT1 and
+/// INT2 is reported twice. Latched interrupts (`set_int_latch(true)`) ensure
+/// short events are not missed between polls.
+///
+/// ```no_run
+/// # async fn example<I: embedded_hal_async::i2c::I2c, D: embedded_hal_async::delay::DelayNs>(i2c: I, delay: D) {
+/// use bmi323::{Bmi323, Polling};
+///
+/// let mut imu = Bmi323::new(i2c, delay, Polling::from_millis(20));
+/// let event = imu.wait_event().await;
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Polling {
+  interval_us: u32,
+}
+
+impl Polling {
+  pub const fn from_micros(interval_us: u32) -> Self {
+    Self { interval_us }
+  }
+
+  pub const fn from_millis(interval_ms: u32) -> Self {
+    Self { interval_us: interval_ms.saturating_mul(1000) }
+  }
+
+  /// Polling interval in microseconds.
+  pub const fn interval_us(&self) -> u32 {
+    self.interval_us
+  }
+}
+
+impl EventSource for Polling {
+  type Error = core::convert::Infallible;
+
+  async fn wait<D: DelayNs>(&mut self, delay: &mut D) -> Result<(), Self::Error> {
+    delay.delay_us(self.interval_us).await;
+    Ok(())
+  }
+
+  fn reads_int2(&self) -> bool {
+    true
+  }
+}
+
 impl<I, D, W, E> Bmi323<I, D, W>
 where
   I: I2c<SevenBitAddress, Error = E>,
   D: DelayNs,
-  W: digital::Wait,
+  W: EventSource,
 {
-  pub async fn wait_event(&mut self) -> Result<Event, Error<E>> {
+  pub async fn wait_event(&mut self) -> Result<Event, Error<E, W::Error>> {
     loop {
       if let Some(evt) = self.dequeue.pop_front() {
         return Ok(evt);
       }
 
-      self.int_pin.wait_for_any_edge().await.map_err(|_| Error::Data)?;
-      self.push_int1_events().await?;
+      self.source.wait(&mut self.delay).await.map_err(Error::Pin)?;
+      self.push_pending_events().await.map_err(Error::widen)?;
       if let Some(evt) = self.dequeue.pop_front() {
         return Ok(evt);
       }
     }
   }
 
-  /// Read and decode INT1 (feature) status and append events to the provided queue.
-  async fn push_int1_events(&mut self) -> Result<(), Error<E>> {
-    let mut tap_event: Option<Event> = None;
-    let mut orient_event: Option<Event> = None;
+  /// Wait for the next event accepted by `filter`; other events are discarded.
+  pub async fn wait_event_matching(&mut self, filter: EventFilter) -> Result<Event, Error<E, W::Error>> {
+    loop {
+      let evt = self.wait_event().await?;
+      if filter.matches(&evt) {
+        return Ok(evt);
+      }
+    }
+  }
 
+  /// Wait for the next event in `handler.filter()` and route it to the matching callback.
+  ///
+  /// Call this in the application loop instead of matching on [`Event`] by hand.
+  pub async fn dispatch<H: EventHandler>(&mut self, handler: &mut H) -> Result<Event, Error<E, W::Error>> {
+    let evt = self.wait_event_matching(handler.filter()).await?;
+    match evt {
+      Event::Tap { kind } => handler.on_tap(kind).await,
+      Event::NoMotion => handler.on_no_motion().await,
+      Event::AnyMotion => handler.on_any_motion().await,
+      Event::Flat => handler.on_flat().await,
+      Event::Orientation { pl, face } => handler.on_orientation(pl, face).await,
+      Event::StepDetector => handler.on_step_detector().await,
+      Event::StepCounter => handler.on_step_counter().await,
+      Event::SigMotion => handler.on_sig_motion().await,
+      Event::Tilt => handler.on_tilt().await,
+      Event::TempDataReady => handler.on_temp_data_ready().await,
+      Event::GyrDataReady => handler.on_gyr_data_ready().await,
+      Event::AccelDataReady => handler.on_accel_data_ready().await,
+      Event::FifoWatermark => handler.on_fifo_watermark().await,
+      Event::FifoFull => handler.on_fifo_full().await,
+      Event::ErrStatus => handler.on_err_status().await,
+    }
+    Ok(evt)
+  }
+
+  /// Read the status register(s) served by the event source and queue their events.
+  async fn push_pending_events(&mut self) -> Result<(), Error<E>> {
     let st = self.get_int1_status().await?;
+    self.push_status_events(st).await?;
+    if self.source.reads_int2() {
+      let st = self.get_int2_status().await?;
+      self.push_status_events(st).await?;
+    }
+    Ok(())
+  }
+
+  /// Decode one `INT_STATUS_INTx` snapshot and append the resulting events to the queue.
+  async fn push_status_events(&mut self, st: IntStatus) -> Result<(), Error<E>> {
     if st.no_motion {
       self.push_event(Event::NoMotion);
     }
@@ -70,28 +355,36 @@ where
       self.push_event(Event::Tilt);
     }
 
-    if st.tap {
+    // Tap and orientation share FEATURE_EVENT_EXT; read it once for both.
+    if st.tap || st.orientation {
       let ext = self.get_feature_event_ext().await?;
-      if ext.s_tap {
-        tap_event = Some(Event::Tap(1));
-      } else if ext.d_tap {
-        tap_event = Some(Event::Tap(2));
-      } else if ext.t_tap {
-        tap_event = Some(Event::Tap(3));
+      if st.tap {
+        for kind in ext.taps() {
+          self.push_event(Event::Tap { kind });
+        }
+      }
+      if st.orientation {
+        self.push_event(Event::Orientation { pl: ext.pl, face: ext.face });
       }
     }
 
-    if st.orientation {
-      let ext = self.get_feature_event_ext().await?;
-      orient_event = Some(Event::Orientation { pl: ext.pl, face: ext.face })
+    if st.err_status {
+      self.push_event(Event::ErrStatus);
     }
-
-    if let Some(e) = tap_event {
-      self.push_event(e);
+    if st.temp_data_ready {
+      self.push_event(Event::TempDataReady);
     }
-
-    if let Some(e) = orient_event {
-      self.push_event(e);
+    if st.gyro_data_ready {
+      self.push_event(Event::GyrDataReady);
+    }
+    if st.accel_data_ready {
+      self.push_event(Event::AccelDataReady);
+    }
+    if st.fifo_watermark {
+      self.push_event(Event::FifoWatermark);
+    }
+    if st.fifo_full {
+      self.push_event(Event::FifoFull);
     }
     Ok(())
   }
@@ -8,7 +8,9 @@ where
   D: DelayNs,
 {
   pub async fn set_any_motion_conf(&mut self, cfg: AnyNoMotionConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::AnyMotion, cfg).await
+    self.write_feature(super::FeatureAddr::AnyMotion, cfg).await?;
+    self.snapshot.any_motion = Some(cfg);
+    Ok(())
   }
 
   pub async fn get_any_motion_conf(&mut self) -> Result<AnyNoMotionConfig, Error<E>> {
@@ -24,8 +26,15 @@ where
     self.set_enabled_features(f).await
   }
 
+  /// Disable Any‑motion on all axes.
+  pub async fn disable_any_motion(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::ANY_MOTION).await
+  }
+
   pub async fn set_no_motion_conf(&mut self, cfg: AnyNoMotionConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::NoMotion, cfg).await
+    self.write_feature(super::FeatureAddr::NoMotion, cfg).await?;
+    self.snapshot.no_motion = Some(cfg);
+    Ok(())
   }
 
   pub async fn get_no_motion_conf(&mut self) -> Result<AnyNoMotionConfig, Error<E>> {
@@ -40,6 +49,11 @@ where
     f.no_motion_z = z;
     self.set_enabled_features(f).await
   }
+
+  /// Disable No‑motion on all axes.
+  pub async fn disable_no_motion(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::NO_MOTION).await
+  }
 }
 
 /// Any-/No‑motion configuration (raw feature fields; no unit conversion).
@@ -74,11 +88,13 @@ where
 /// - For “no‑motion”, consider longer `duration` to avoid chattering when near threshold.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 6)]
 pub struct AnyNoMotionConfig {
   /// Slope threshold (12 bits, LSB = 1/512 g). g ≈ raw/512. Default = 10.
   /// Examples: 256 → 0.50 g, 512 → 1.00 g, 1024 → 2.00 g, 2048 → 4.00 g, 4095 → ≈7.998 g.
   #[bits(12)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<12>"))]
   pub slope_threshold: u16,
   /// Allow hardware to update its internal acceleration reference (baseline).
   #[bits(1)]
@@ -87,15 +103,18 @@ pub struct AnyNoMotionConfig {
   /// Examples: 256 → 0.50 g, 512 → 1.00 g, 768 → 1.50 g, 1023 → ≈1.998 g.
   #[skip(3)]
   #[bits(10)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<10>"))]
   pub hysteresis: u16,
   /// Condition duration (13 bits, LSB = 20 ms). seconds ≈ raw/50. Default ≈ 10.
   /// Examples: 50 → 1.0 s, 25 → 0.5 s, 10 → 0.2 s.
   #[skip(6)]
   #[bits(13)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms20::<13>"))]
   pub duration: u16,
   /// Post‑condition wait (3 bits, LSB = 20 ms). seconds ≈ raw/50. Default = 3.
   /// Examples: 1 → 20 ms, 3 → 60 ms, 7 → 140 ms (max).
   #[bits(3)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms20::<3>"))]
   pub wait_time: u8,
 }
 
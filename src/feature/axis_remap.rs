@@ -1,6 +1,13 @@
 use embedded_hal_async::{delay::DelayNs, i2c::*};
 
-use crate::{Bmi323, Error, defs::*};
+use micromath::vector::Vector3d;
+
+use crate::{
+  accel::{AccelConfig, AccelPowerMode},
+  defs::*,
+  gyro::{GyroConfig, GyroPowerMode},
+  Bmi323, ConfigError, Error, Operation,
+};
 
 impl<I, D, W, E> Bmi323<I, D, W>
 where
@@ -12,6 +19,7 @@ where
     self.write_feature(super::FeatureAddr::AxisRemap, map).await?;
     self.write_u16(Reg::Cmd, Command::AxisMapUpdate.into()).await?;
     self.wait_axis_map_complete().await?;
+    self.snapshot.axis_remap = Some(map);
     Ok(())
   }
 
@@ -20,18 +28,91 @@ where
     self.read_feature(super::FeatureAddr::AxisRemap).await
   }
 
-  /// Poll `FEATURE_ENGINE_STATUS` for axis-map complete bit.
+  /// Disable both sensors, apply the remap, then restore the previous sensor
+  /// configurations.
+  ///
+  /// [`set_axis_remap`](Self::set_axis_remap) is rejected while a sensor is
+  /// active. The sensors are restored even if the remap fails, and the remap
+  /// error is returned.
+  pub async fn set_axis_remap_safe(&mut self, map: AxisRemap) -> Result<(), Error<E>> {
+    let accel = self.get_accel_conf().await?;
+    let gyro = self.get_gyro_conf().await?;
+    let accel_off = AccelConfig { mode: AccelPowerMode::Disable, ..accel };
+    let gyro_off = GyroConfig { mode: GyroPowerMode::Disable, ..gyro };
+    self.set_accel_conf(accel_off).await?;
+    self.set_gyro_conf(gyro_off).await?;
+    let res = self.set_axis_remap(map).await;
+    self.set_accel_conf(accel).await?;
+    self.set_gyro_conf(gyro).await?;
+    res
+  }
+
+  /// Apply a board mounting rotation.
+  ///
+  /// Axis-aligned rotations use the on-chip remap (via
+  /// [`set_axis_remap_safe`](Self::set_axis_remap_safe)) when the feature
+  /// engine is enabled, so feature detectors see board axes too, and the
+  /// software transform is cleared. Any other rotation is applied as the
+  /// [frame transform](Self::set_frame_transform), after resetting the
+  /// on-chip remap if the engine is enabled.
+  pub async fn set_mounting(&mut self, rot: Rotation) -> Result<(), Error<E>> {
+    let engine = self.is_feature_engine_enabled().await?;
+    match rot.to_axis_remap() {
+      Ok(map) if engine => {
+        self.set_axis_remap_safe(map).await?;
+        self.frame = None;
+      }
+      _ => {
+        // Drop an earlier on-chip remap so samples are not rotated twice.
+        if engine {
+          self.set_axis_remap_safe(AxisRemap::default()).await?;
+        }
+        self.frame = Some(rot);
+      }
+    }
+    Ok(())
+  }
+
+  /// Set the driver-side chip-to-board rotation.
+  ///
+  /// When set, register reads ([`get_accel_data`](Self::get_accel_data),
+  /// [`get_gyro_data`](Self::get_gyro_data), the raw and combined variants)
+  /// and [`parse_fifo`](Self::parse_fifo) return board-frame samples. The
+  /// on-chip remap is not touched, so do not combine both for one mounting.
+  pub fn set_frame_transform(&mut self, rot: Option<Rotation>) {
+    self.frame = rot;
+  }
+
+  pub fn frame_transform(&self) -> Option<Rotation> {
+    self.frame
+  }
+
+  pub(crate) fn to_board_raw(&self, v: Vector3d<i16>) -> Vector3d<i16> {
+    self.frame.map_or(v, |r| r.apply_raw(v))
+  }
+
+  pub(crate) fn to_board(&self, v: Vector3d<f32>) -> Vector3d<f32> {
+    self.frame.map_or(v, |r| r.apply(v))
+  }
+
+  /// Poll `FEATURE_IO1` for the axis-map complete bit.
+  ///
+  /// The engine refuses the command while a sensor is active or a
+  /// self-test/calibration runs, reporting `AxisMapCmdNotProcessed`.
   async fn wait_axis_map_complete(&mut self) -> Result<(), Error<E>> {
     let mut tries = 0;
     loop {
-      let v: FeatureEngineStatusReg = self.read(Reg::FeatureEngineStatus).await?;
+      let v = self.get_feature_io1().await?;
       if v.axis_map_complete {
         return Ok(());
       }
+      if v.error_status == super::FeatureIoError::AxisMapCmdNotProcessed {
+        return Err(Error::Config(ConfigError::Rejected(v.error_status)));
+      }
       self.delay.delay_ms(2).await;
       tries += 1;
       if tries > 100 {
-        return Err(Error::Data);
+        return Err(Error::Timeout(Operation::AxisMapUpdate));
       }
     }
   }
@@ -39,16 +120,7 @@ where
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
-#[packbits::pack(bytes = 2)]
-struct FeatureEngineStatusReg {
-  #[skip(10)]
-  pub axis_map_complete: bool,
-  #[bits(2)]
-  pub engine_state: super::FeatureEngineState,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AxisOrder {
   /// X => X, Y => Y, Z => Z
   XYZ = 0,
@@ -87,6 +159,7 @@ impl core::convert::TryFrom<u8> for AxisOrder {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 2)]
 pub struct AxisRemap {
   #[bits(3)]
@@ -106,4 +179,239 @@ impl AxisRemap {
   pub const fn new(order: AxisOrder, invert_x: bool, invert_y: bool, invert_z: bool) -> Self {
     Self { order, invert_x, invert_y, invert_z }
   }
+
+  /// Build a remap from the rotation taking chip coordinates to board
+  /// coordinates (`board = m · chip`).
+  ///
+  /// `m` must be a signed permutation matrix with determinant +1; mirrored
+  /// frames are rejected with [`ConfigError::NotRotation`].
+  ///
+  /// ```
+  /// use bmi323::axis_remap::{AxisOrder, AxisRemap};
+  ///
+  /// // Chip rotated 90° about Z: board X = chip Y, board Y = -chip X.
+  /// let map = AxisRemap::from_matrix([[0, 1, 0], [-1, 0, 0], [0, 0, 1]]).unwrap();
+  /// assert_eq!(map, AxisRemap::new(AxisOrder::YXZ, false, true, false));
+  /// ```
+  pub fn from_matrix(m: [[i8; 3]; 3]) -> Result<Self, ConfigError> {
+    let mut src = [0u8; 3];
+    let mut neg = [false; 3];
+    let mut used = [false; 3];
+    for (row, out) in m.iter().enumerate() {
+      let mut nonzero = out.iter().enumerate().filter(|(_, v)| **v != 0);
+      let (col, v) = match (nonzero.next(), nonzero.next()) {
+        (Some((col, &v)), None) if v.unsigned_abs() == 1 && !used[col] => (col, v),
+        _ => return Err(ConfigError::NotRotation),
+      };
+      used[col] = true;
+      src[row] = col as u8;
+      neg[row] = v < 0;
+    }
+    if det(m.map(|r| r.map(f32::from))) < 0.0 {
+      return Err(ConfigError::NotRotation);
+    }
+    let order = match src {
+      [0, 1, 2] => AxisOrder::XYZ,
+      [1, 0, 2] => AxisOrder::YXZ,
+      [0, 2, 1] => AxisOrder::XZY,
+      [2, 0, 1] => AxisOrder::ZXY,
+      [1, 2, 0] => AxisOrder::YZX,
+      _ => AxisOrder::ZYX,
+    };
+    Ok(Self::new(order, neg[0], neg[1], neg[2]))
+  }
+
+  /// Build a remap from where the chip's X and Z axes point on the board.
+  ///
+  /// ```
+  /// use bmi323::axis_remap::{AxisRemap, BoardDirection};
+  ///
+  /// // Chip mounted upside down, X pointing to the board's left.
+  /// let map = AxisRemap::from_directions(BoardDirection::Left, BoardDirection::Down).unwrap();
+  /// assert_eq!(map.matrix(), [[-1, 0, 0], [0, 1, 0], [0, 0, -1]]);
+  /// ```
+  pub fn from_directions(chip_x: BoardDirection, chip_z: BoardDirection) -> Result<Self, ConfigError> {
+    Self::from_matrix(direction_matrix(chip_x, chip_z)?)
+  }
+
+  /// The rotation applied by this remap (`board = m · chip`).
+  pub fn matrix(self) -> [[i8; 3]; 3] {
+    let src = match self.order {
+      AxisOrder::XYZ => [0, 1, 2],
+      AxisOrder::YXZ => [1, 0, 2],
+      AxisOrder::XZY => [0, 2, 1],
+      AxisOrder::ZXY => [2, 0, 1],
+      AxisOrder::YZX => [1, 2, 0],
+      AxisOrder::ZYX => [2, 1, 0],
+    };
+    let neg = [self.invert_x, self.invert_y, self.invert_z];
+    let mut m = [[0i8; 3]; 3];
+    for row in 0..3 {
+      m[row][src[row]] = if neg[row] { -1 } else { 1 };
+    }
+    m
+  }
+}
+
+/// Direction on the board, in a right-handed frame: X right, Y forward, Z up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardDirection {
+  /// +X
+  Right,
+  /// -X
+  Left,
+  /// +Y
+  Forward,
+  /// -Y
+  Back,
+  /// +Z
+  Up,
+  /// -Z
+  Down,
+}
+
+impl BoardDirection {
+  pub const fn vector(self) -> [i8; 3] {
+    match self {
+      BoardDirection::Right => [1, 0, 0],
+      BoardDirection::Left => [-1, 0, 0],
+      BoardDirection::Forward => [0, 1, 0],
+      BoardDirection::Back => [0, -1, 0],
+      BoardDirection::Up => [0, 0, 1],
+      BoardDirection::Down => [0, 0, -1],
+    }
+  }
+}
+
+/// Chip-to-board matrix whose columns are the board directions of the chip
+/// axes; chip Y completes the right-handed frame (`y = z × x`).
+fn direction_matrix(chip_x: BoardDirection, chip_z: BoardDirection) -> Result<[[i8; 3]; 3], ConfigError> {
+  let x = chip_x.vector();
+  let z = chip_z.vector();
+  let y = [
+    z[1] * x[2] - z[2] * x[1],
+    z[2] * x[0] - z[0] * x[2],
+    z[0] * x[1] - z[1] * x[0],
+  ];
+  if y == [0; 3] {
+    return Err(ConfigError::NotRotation);
+  }
+  Ok([[x[0], y[0], z[0]], [x[1], y[1], z[1]], [x[2], y[2], z[2]]])
+}
+
+fn det(m: [[f32; 3]; 3]) -> f32 {
+  m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+    + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+}
+
+/// Largest deviation from orthonormality accepted by [`Rotation::from_matrix`].
+const ROTATION_TOLERANCE: f32 = 1e-3;
+
+/// Chip-to-board rotation applied in software (`board = m · chip`).
+///
+/// Covers mountings the on-chip [`AxisRemap`] cannot express, e.g. a chip
+/// placed at 45° on the board. Use [`Rotation::to_axis_remap`] to check
+/// whether the hardware remap can be used instead.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "[[f32; 3]; 3]", into = "[[f32; 3]; 3]"))]
+pub struct Rotation {
+  m: [[f32; 3]; 3],
+}
+
+impl Rotation {
+  pub const IDENTITY: Self = Self { m: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]] };
+
+  /// Validate that `m` is finite and orthonormal with determinant +1 (within 1e-3).
+  pub fn from_matrix(m: [[f32; 3]; 3]) -> Result<Self, ConfigError> {
+    // NaN compares false against the tolerance below, so reject it up front.
+    if !m.iter().flatten().all(|v| v.is_finite()) {
+      return Err(ConfigError::NotRotation);
+    }
+    for i in 0..3 {
+      for j in 0..3 {
+        let dot: f32 = (0..3).map(|k| m[k][i] * m[k][j]).sum();
+        let expected = if i == j { 1.0 } else { 0.0 };
+        if (dot - expected).abs() > ROTATION_TOLERANCE {
+          return Err(ConfigError::NotRotation);
+        }
+      }
+    }
+    if det(m) < 0.0 {
+      return Err(ConfigError::NotRotation);
+    }
+    Ok(Self { m })
+  }
+
+  /// Rotation from where the chip's X and Z axes point on the board.
+  pub fn from_directions(chip_x: BoardDirection, chip_z: BoardDirection) -> Result<Self, ConfigError> {
+    Ok(Self { m: direction_matrix(chip_x, chip_z)?.map(|r| r.map(f32::from)) })
+  }
+
+  pub fn matrix(&self) -> [[f32; 3]; 3] {
+    self.m
+  }
+
+  /// The equivalent on-chip remap, or [`ConfigError::NotAxisAligned`] if the
+  /// rotation is not a multiple of 90° about the chip axes.
+  pub fn to_axis_remap(&self) -> Result<AxisRemap, ConfigError> {
+    let mut m = [[0i8; 3]; 3];
+    for (out, row) in m.iter_mut().zip(self.m) {
+      for (o, v) in out.iter_mut().zip(row) {
+        *o = match v {
+          v if (v - 1.0).abs() <= ROTATION_TOLERANCE => 1,
+          v if (v + 1.0).abs() <= ROTATION_TOLERANCE => -1,
+          v if v.abs() <= ROTATION_TOLERANCE => 0,
+          _ => return Err(ConfigError::NotAxisAligned),
+        };
+      }
+    }
+    AxisRemap::from_matrix(m)
+  }
+
+  /// Rotate a chip-frame vector into the board frame.
+  pub fn apply(&self, v: Vector3d<f32>) -> Vector3d<f32> {
+    let [a, b, c] = self.m;
+    Vector3d {
+      x: a[0] * v.x + a[1] * v.y + a[2] * v.z,
+      y: b[0] * v.x + b[1] * v.y + b[2] * v.z,
+      z: c[0] * v.x + c[1] * v.y + c[2] * v.z,
+    }
+  }
+
+  /// Rotate a raw chip-frame sample, rounding and saturating to `i16`.
+  pub fn apply_raw(&self, v: Vector3d<i16>) -> Vector3d<i16> {
+    let r = self.apply(Vector3d { x: v.x as f32, y: v.y as f32, z: v.z as f32 });
+    let round = |f: f32| (if f < 0.0 { f - 0.5 } else { f + 0.5 }) as i16;
+    Vector3d { x: round(r.x), y: round(r.y), z: round(r.z) }
+  }
+}
+
+impl Default for Rotation {
+  fn default() -> Self {
+    Self::IDENTITY
+  }
+}
+
+impl TryFrom<[[f32; 3]; 3]> for Rotation {
+  type Error = ConfigError;
+
+  fn try_from(m: [[f32; 3]; 3]) -> Result<Self, Self::Error> {
+    Self::from_matrix(m)
+  }
+}
+
+impl From<Rotation> for [[f32; 3]; 3] {
+  fn from(rot: Rotation) -> Self {
+    rot.m
+  }
+}
+
+impl From<AxisRemap> for Rotation {
+  fn from(map: AxisRemap) -> Self {
+    Self { m: map.matrix().map(|r| r.map(f32::from)) }
+  }
 }
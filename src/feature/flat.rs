@@ -8,7 +8,9 @@ where
   D: DelayNs,
 {
   pub async fn set_flat_conf(&mut self, cfg: FlatConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::Flat, cfg).await
+    self.write_feature(super::FeatureAddr::Flat, cfg).await?;
+    self.snapshot.flat = Some(cfg);
+    Ok(())
   }
 
   pub async fn get_flat_conf(&mut self) -> Result<FlatConfig, Error<E>> {
@@ -21,6 +23,11 @@ where
     f.flat = true;
     self.set_enabled_features(f).await
   }
+
+  /// Disable flat detection.
+  pub async fn disable_flat(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::FLAT).await
+  }
 }
 
 /// Flat (device face‑up/face‑down) configuration.
@@ -42,6 +49,7 @@ where
 /// - Use `blocking` and slope threshold/hysteresis when the device vibrates to reduce chattering.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 4)]
 pub struct FlatConfig {
   /// Theta (0..=63). Degrees ≈ atan(sqrt(raw/64)) × 180/π. Default = 8 (~20°).
@@ -63,12 +71,15 @@ pub struct FlatConfig {
   /// - raw 10 → 200 ms
   /// - raw 25 → 500 ms
   /// - raw 50 → 1.00 s
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms20::<8>"))]
   pub hold_time: u8,
   /// Slope threshold (LSB = 1/512 g). g ≈ raw/512. Default = 0xCD (≈400.365 mg).
   /// Examples: 1 → ≈0.002 g, 10 → ≈0.020 g, 50 → ≈0.098 g, 255 → ≈0.498 g.
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<8>"))]
   pub slope_threshold: u8,
   /// Hysteresis (LSB = 1/512 g). g ≈ raw/512.
   /// Examples: 1 → ≈0.002 g, 10 → ≈0.020 g, 50 → ≈0.098 g, 255 → ≈0.498 g.
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<8>"))]
   pub hysterisis: u8,
 }
 
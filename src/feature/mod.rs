@@ -17,7 +17,14 @@
 
 use embedded_hal_async::{delay::DelayNs, i2c::*};
 
-use super::{Bmi323, Error, defs::*};
+use super::{Bmi323, Error, Operation, defs::*};
+use any_no_motion::AnyNoMotionConfig;
+use axis_remap::AxisRemap;
+use flat::FlatConfig;
+use orientation::OrientationConfig;
+use sig_motion::SigMotionConfig;
+use tap::TapConfig;
+use tilt::TiltConfig;
 
 // Submodules with per-feature configuration APIs
 pub mod any_no_motion;
@@ -54,22 +61,27 @@ where
       self.delay.delay_us(100_000).await;
 
       let v: FeatureIo1 = self.read(Reg::FeatureIo1).await?;
-      if v.error_status == FeatureIoError::Activated {
-        break;
+      match v.error_status {
+        FeatureIoError::Activated => break,
+        FeatureIoError::ConfigStringWrong => return Err(Error::Init),
+        _ => {}
       }
 
       tries += 1;
       if tries > 10 {
-        return Err(Error::Init);
+        return Err(Error::Timeout(Operation::FeatureEngineActivation));
       }
     }
 
+    self.snapshot.feature_engine = true;
     Ok(())
   }
 
   /// Disable the feature engine. A soft reset or power‑cycle is required before enabling again.
   pub async fn disable_feature_engine(&mut self) -> Result<(), Error<E>> {
-    self.write_u16(Reg::FeatureCtrl, 0).await
+    self.write_u16(Reg::FeatureCtrl, 0).await?;
+    self.snapshot.feature_engine = false;
+    Ok(())
   }
 
   pub async fn is_feature_engine_enabled(&mut self) -> Result<bool, Error<E>> {
@@ -80,15 +92,12 @@ where
   where
     T: TryInto<[u8; N]>,
   {
-    let bytes = v.try_into().map_err(|_| Error::Data)?;
+    let bytes = v.try_into().map_err(|_| Error::InvalidFeatureValue(addr))?;
     self.write_feature_bytes(addr, &bytes).await
   }
 
   pub(crate) async fn write_feature_bytes(&mut self, addr: FeatureAddr, v: &[u8]) -> Result<(), Error<E>> {
-    self.wait_feature_data_ready().await?;
-    self.write_bytes(Reg::FeatureDataAddr, &[addr as u8, 0]).await?;
-    self.write_bytes(Reg::FeatureDataTx, v).await?;
-    Ok(())
+    self.write_feature_at(addr as u16, v).await
   }
 
   /// Read and unpack a feature configuration block into a typed value.
@@ -98,43 +107,196 @@ where
   {
     let mut bytes = [0u8; N];
     self.read_feature_bytes(addr, &mut bytes).await?;
-    bytes.try_into().map_err(|_| Error::Data)
+    bytes.try_into().map_err(|_| Error::InvalidFeatureValue(addr))
   }
 
   /// Read a contiguous block of feature words into `out`.
   pub(crate) async fn read_feature_bytes(&mut self, addr: FeatureAddr, out: &mut [u8]) -> Result<(), Error<E>> {
-    // Per datasheet §6.2 (Extended Register Map): see comment in write_feature_bytes.
-    if out.len() % 2 != 0 {
-      return Err(Error::Data);
+    self.read_feature_at(addr as u16, out).await
+  }
+
+  /// Read `out.len()` words of extended (feature) memory starting at word address `addr`.
+  ///
+  /// Any word address below [`FEATURE_MEMORY_WORDS`] is accepted, including words
+  /// without a [`FeatureAddr`] name. Blocks larger than one transfer are split
+  /// into chunks. Returns [`Error::FeatureOutOfBounds`] if the block does not
+  /// fit the memory or the device flags `FEATURE_DATA_STATUS.out_of_bound_err`.
+  pub async fn read_feature_words(&mut self, addr: u16, out: &mut [u16]) -> Result<(), Error<E>> {
+    check_feature_range(addr, out.len())?;
+    let mut bytes = [0u8; FEATURE_CHUNK_WORDS * 2];
+    for (i, chunk) in out.chunks_mut(FEATURE_CHUNK_WORDS).enumerate() {
+      let b = &mut bytes[..chunk.len() * 2];
+      self.read_feature_at(addr + (i * FEATURE_CHUNK_WORDS) as u16, b).await?;
+      for (w, le) in chunk.iter_mut().zip(b.chunks_exact(2)) {
+        *w = u16::from_le_bytes([le[0], le[1]]);
+      }
+    }
+    Ok(())
+  }
+
+  /// Write `words` to extended (feature) memory starting at word address `addr`.
+  ///
+  /// Same addressing, chunking and bounds checks as [`read_feature_words`](Self::read_feature_words).
+  /// Writes to named blocks bypass validation and the configuration snapshot.
+  pub async fn write_feature_words(&mut self, addr: u16, words: &[u16]) -> Result<(), Error<E>> {
+    check_feature_range(addr, words.len())?;
+    let mut bytes = [0u8; FEATURE_CHUNK_WORDS * 2];
+    for (i, chunk) in words.chunks(FEATURE_CHUNK_WORDS).enumerate() {
+      for (le, w) in bytes.chunks_exact_mut(2).zip(chunk) {
+        le.copy_from_slice(&w.to_le_bytes());
+      }
+      let a = addr + (i * FEATURE_CHUNK_WORDS) as u16;
+      self.write_feature_at(a, &bytes[..chunk.len() * 2]).await?;
+    }
+    Ok(())
+  }
+
+  /// Read a single feature word.
+  pub async fn read_feature_word(&mut self, addr: u16) -> Result<u16, Error<E>> {
+    let mut w = [0u16; 1];
+    self.read_feature_words(addr, &mut w).await?;
+    Ok(w[0])
+  }
+
+  /// Write a single feature word.
+  pub async fn write_feature_word(&mut self, addr: u16, value: u16) -> Result<(), Error<E>> {
+    self.write_feature_words(addr, &[value]).await
+  }
+
+  /// Read-modify-write a feature word; returns the value written.
+  pub async fn update_feature_word(&mut self, addr: u16, f: impl FnOnce(u16) -> u16) -> Result<u16, Error<E>> {
+    let v = f(self.read_feature_word(addr).await?);
+    self.write_feature_word(addr, v).await?;
+    Ok(v)
+  }
+
+  /// Read the field selected by `mask` from a feature word, shifted down to bit 0.
+  ///
+  /// E.g. `get_feature_field(0x10, 0x03FF)` reads the 10-bit step-counter watermark.
+  pub async fn get_feature_field(&mut self, addr: u16, mask: u16) -> Result<u16, Error<E>> {
+    let w = self.read_feature_word(addr).await? & mask;
+    // An empty mask selects no bits (and would overflow the shift).
+    Ok(w.checked_shr(mask.trailing_zeros()).unwrap_or(0))
+  }
+
+  /// Set the field selected by `mask` in a feature word, leaving other bits untouched.
+  ///
+  /// `value` is shifted up to the position of `mask`; bits that do not fit
+  /// are dropped.
+  pub async fn set_feature_field(&mut self, addr: u16, mask: u16, value: u16) -> Result<(), Error<E>> {
+    let bits = value.checked_shl(mask.trailing_zeros()).unwrap_or(0) & mask;
+    self.update_feature_word(addr, |w| (w & !mask) | bits).await?;
+    Ok(())
+  }
+
+  async fn write_feature_at(&mut self, addr: u16, v: &[u8]) -> Result<(), Error<E>> {
+    if !v.len().is_multiple_of(2) {
+      return Err(Error::InvalidLength(v.len()));
+    }
+    check_feature_range(addr, v.len() / 2)?;
+    for (i, chunk) in v.chunks(FEATURE_CHUNK_WORDS * 2).enumerate() {
+      self.wait_feature_data_ready().await?;
+      let a = addr + (i * FEATURE_CHUNK_WORDS) as u16;
+      self.write_u16(Reg::FeatureDataAddr, a).await?;
+      self.write_bytes(Reg::FeatureDataTx, chunk).await?;
     }
+    self.check_feature_bounds().await
+  }
 
-    self.wait_feature_data_ready().await?;
-    self.write_bytes(Reg::FeatureDataAddr, &[addr as u8, 0]).await?;
-    self.read_bytes(Reg::FeatureDataTx, out).await
+  async fn read_feature_at(&mut self, addr: u16, out: &mut [u8]) -> Result<(), Error<E>> {
+    // Per datasheet §6.2 (Extended Register Map): wait for data_tx_ready, set
+    // the word address, then transfer through FEATURE_DATA_TX.
+    if !out.len().is_multiple_of(2) {
+      return Err(Error::InvalidLength(out.len()));
+    }
+    check_feature_range(addr, out.len() / 2)?;
+    for (i, chunk) in out.chunks_mut(FEATURE_CHUNK_WORDS * 2).enumerate() {
+      self.wait_feature_data_ready().await?;
+      let a = addr + (i * FEATURE_CHUNK_WORDS) as u16;
+      self.write_u16(Reg::FeatureDataAddr, a).await?;
+      self.read_bytes(Reg::FeatureDataTx, chunk).await?;
+    }
+    self.check_feature_bounds().await
+  }
+
+  async fn check_feature_bounds(&mut self) -> Result<(), Error<E>> {
+    if self.get_feature_data_status().await?.out_of_bound_err {
+      return Err(Error::FeatureOutOfBounds);
+    }
+    Ok(())
   }
 
   /// Read the current feature enable bitmask (`FEATURE_IO0`).
-  pub(crate) async fn get_enabled_features(&mut self) -> Result<Features, Error<E>> {
+  pub async fn get_enabled_features(&mut self) -> Result<Features, Error<E>> {
     self.read(Reg::FeatureIo0).await
   }
 
-  /// Write the feature enable bitmask (`FEATURE_IO0`).
+  /// Write the feature enable bitmask (`FEATURE_IO0`), replacing the current one.
   ///
   /// Example: enable Any‑motion on all axes and Orientation.
   ///
   /// ```no_run
   /// # async fn demo<E>(bmi: &mut bmi323::Bmi323<impl embedded_hal_async::i2c::I2c<embedded_hal_async::i2c::SevenBitAddress, Error=E>, impl embedded_hal_async::delay::DelayNs>) -> Result<(), bmi323::Error<E>> {
-  /// let mut feats = bmi323::feature::Features::none();
-  /// feats.any_motion_x = true;
-  /// feats.any_motion_y = true;
-  /// feats.any_motion_z = true;
-  /// feats.orientation = true;
-  /// bmi.set_enabled_features(feats).await?;
+  /// use bmi323::Features;
+  /// bmi.set_enabled_features(Features::ANY_MOTION | Features::ORIENTATION).await?;
   /// # Ok(()) }
   /// ```
-  pub(crate) async fn set_enabled_features(&mut self, v: Features) -> Result<(), Error<E>> {
+  pub async fn set_enabled_features(&mut self, v: Features) -> Result<(), Error<E>> {
     self.write(Reg::FeatureIo0, v).await?;
-    self.write_u16(Reg::FeatureIoStatus, 0x1).await
+    self.write_u16(Reg::FeatureIoStatus, 0x1).await?;
+    self.snapshot.features = Some(v);
+    Ok(())
+  }
+
+  /// Enable every feature set in `mask`, leaving the others as they are.
+  pub async fn enable_features(&mut self, mask: Features) -> Result<(), Error<E>> {
+    let f = self.get_enabled_features().await?;
+    self.set_enabled_features(f | mask).await
+  }
+
+  /// Disable every feature set in `mask`, leaving the others as they are.
+  pub async fn disable_features(&mut self, mask: Features) -> Result<(), Error<E>> {
+    let f = self.get_enabled_features().await?;
+    self.set_enabled_features(f & !mask).await
+  }
+
+  /// Write all configurations in `cfg`, then the enable mask in a single update.
+  ///
+  /// The feature engine is enabled first if needed. Configuration blocks set
+  /// to `None` are left untouched; features missing from `cfg.enabled` are
+  /// disabled.
+  pub async fn apply_features(&mut self, cfg: FeatureSettings) -> Result<(), Error<E>> {
+    if !self.is_feature_engine_enabled().await? {
+      self.enable_feature_engine().await?;
+    }
+    if let Some(v) = cfg.axis_remap {
+      self.set_axis_remap(v).await?;
+    }
+    if let Some(v) = cfg.any_motion {
+      self.set_any_motion_conf(v).await?;
+    }
+    if let Some(v) = cfg.no_motion {
+      self.set_no_motion_conf(v).await?;
+    }
+    if let Some(v) = cfg.flat {
+      self.set_flat_conf(v).await?;
+    }
+    if let Some(v) = cfg.orientation {
+      self.set_orientation_conf(v).await?;
+    }
+    if let Some(v) = cfg.sig_motion {
+      self.set_sign_motion_conf(v).await?;
+    }
+    if let Some(v) = cfg.tap {
+      self.set_tap_conf(v).await?;
+    }
+    if let Some(v) = cfg.tilt {
+      self.set_tilt_conf(v).await?;
+    }
+    if let Some(v) = cfg.step_watermark {
+      self.set_step_watermark(v).await?;
+    }
+    self.set_enabled_features(cfg.enabled).await
   }
 
   pub async fn get_feature_data_status(&mut self) -> Result<FeatureDataStatus, Error<E>> {
@@ -145,6 +307,11 @@ where
     self.read(Reg::FeatureEngineStatus).await
   }
 
+  /// Read `FEATURE_IO1` (engine error code, state and self-test/calibration flags).
+  pub async fn get_feature_io1(&mut self) -> Result<FeatureIo1, Error<E>> {
+    self.read(Reg::FeatureIo1).await
+  }
+
   /// Internal: wait until the feature data interface is ready for I/O.
   ///
   /// Datasheet §6.2 requires `FEATURE_DATA_STATUS.data_tx_ready == 1` before
@@ -154,14 +321,14 @@ where
     loop {
       let status = self.get_feature_data_status().await?;
       if status.out_of_bound_err {
-        // Previous operation signaled an out‑of‑bound access; propagate as data error.
-        return Err(Error::Data);
+        // Previous operation signaled an out‑of‑bound access.
+        return Err(Error::FeatureOutOfBounds);
       }
       if status.data_tx_ready {
         return Ok(());
       }
       if tries > 100 {
-        return Err(Error::Data);
+        return Err(Error::Timeout(Operation::FeatureDataReady));
       }
       self.delay.delay_ms(2).await;
       tries += 1;
@@ -169,7 +336,22 @@ where
   }
 }
 
+/// Size of the extended (feature) memory in 16-bit words.
+pub const FEATURE_MEMORY_WORDS: u16 = 0x800;
+
+/// Words moved per FEATURE_DATA_TX transfer (the device burst limit is 32 bytes).
+const FEATURE_CHUNK_WORDS: usize = 15;
+
+fn check_feature_range<E>(addr: u16, words: usize) -> Result<(), Error<E>> {
+  if addr as usize + words > FEATURE_MEMORY_WORDS as usize {
+    return Err(Error::FeatureOutOfBounds);
+  }
+  Ok(())
+}
+
 /// Feature memory base addresses (used with FEATURE_DATA_ADDR/TX).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[repr(u8)]
 pub enum FeatureAddr {
   AxisRemap = 0x03,
@@ -207,6 +389,7 @@ impl From<FeatureAddr> for u8 {
 /// - `AccelOver1p5gOrSlope`: like above, but uses the full slope threshold.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlockingRule {
   Disabled = 0,
   AccelOver1p5g = 1,
@@ -242,6 +425,7 @@ impl TryFrom<u8> for BlockingRule {
 /// of the underlying feature.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MotionTiming {
   pub duration_s: f32,
   pub wait_time_s: f32,
@@ -253,9 +437,11 @@ impl MotionTiming {
   }
 }
 
+/// Decoded `FEATURE_IO1`: feature engine error code, state and completion flags.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 2)]
-struct FeatureIo1 {
+pub struct FeatureIo1 {
   #[bits(4)]
   pub error_status: FeatureIoError,
   pub sc_st_complete: bool,
@@ -347,6 +533,8 @@ impl TryFrom<u8> for FeatureEngineState {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 2)]
 pub struct Features {
   /// Assert No‑motion when the X‑axis slope stays below the programmed threshold.
@@ -379,9 +567,25 @@ pub struct Features {
   pub tap_double: bool,
   /// Triple tap event.
   pub tap_triple: bool,
+  /// I3C TC-sync (see [`crate::i3c`]).
+  pub i3c_sync: bool,
 }
 
 impl Features {
+  /// Any‑motion on all axes.
+  pub const ANY_MOTION: Self = Self { any_motion_x: true, any_motion_y: true, any_motion_z: true, ..Self::none() };
+  /// No‑motion on all axes.
+  pub const NO_MOTION: Self = Self { no_motion_x: true, no_motion_y: true, no_motion_z: true, ..Self::none() };
+  pub const FLAT: Self = Self { flat: true, ..Self::none() };
+  pub const ORIENTATION: Self = Self { orientation: true, ..Self::none() };
+  pub const STEP_DETECTOR: Self = Self { step_detector: true, ..Self::none() };
+  pub const STEP_COUNTER: Self = Self { step_counter: true, ..Self::none() };
+  pub const SIG_MOTION: Self = Self { sig_motion: true, ..Self::none() };
+  pub const TILT: Self = Self { tilt: true, ..Self::none() };
+  /// Single, double and triple tap.
+  pub const TAP: Self = Self { tap_single: true, tap_double: true, tap_triple: true, ..Self::none() };
+  pub const I3C_SYNC: Self = Self { i3c_sync: true, ..Self::none() };
+
   /// All features disabled (convenient starting point).
   pub const fn none() -> Self {
     Self {
@@ -400,11 +604,108 @@ impl Features {
       tap_single: false,
       tap_double: false,
       tap_triple: false,
+      i3c_sync: false,
+    }
+  }
+
+  /// Raw `FEATURE_IO0` value.
+  pub fn bits(self) -> u16 {
+    u16::from_le_bytes(self.into())
+  }
+
+  /// Mask from a raw `FEATURE_IO0` value.
+  pub fn from_bits(bits: u16) -> Self {
+    Self::from(bits.to_le_bytes())
+  }
+
+  pub fn is_empty(self) -> bool {
+    self.bits() == 0
+  }
+
+  /// True if every feature in `other` is also set in `self`.
+  pub fn contains(self, other: Self) -> bool {
+    self.bits() & other.bits() == other.bits()
+  }
+}
+
+impl core::ops::BitOr for Features {
+  type Output = Self;
+  fn bitor(self, rhs: Self) -> Self {
+    Self::from_bits(self.bits() | rhs.bits())
+  }
+}
+
+impl core::ops::BitAnd for Features {
+  type Output = Self;
+  fn bitand(self, rhs: Self) -> Self {
+    Self::from_bits(self.bits() & rhs.bits())
+  }
+}
+
+impl core::ops::BitXor for Features {
+  type Output = Self;
+  fn bitxor(self, rhs: Self) -> Self {
+    Self::from_bits(self.bits() ^ rhs.bits())
+  }
+}
+
+impl core::ops::Not for Features {
+  type Output = Self;
+  fn not(self) -> Self {
+    Self::from_bits(!self.bits())
+  }
+}
+
+impl core::ops::BitOrAssign for Features {
+  fn bitor_assign(&mut self, rhs: Self) {
+    *self = *self | rhs;
+  }
+}
+
+impl core::ops::BitAndAssign for Features {
+  fn bitand_assign(&mut self, rhs: Self) {
+    *self = *self & rhs;
+  }
+}
+
+/// Feature configurations and enable mask applied together by [`Bmi323::apply_features`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FeatureSettings {
+  pub axis_remap: Option<AxisRemap>,
+  pub any_motion: Option<AnyNoMotionConfig>,
+  pub no_motion: Option<AnyNoMotionConfig>,
+  pub flat: Option<FlatConfig>,
+  pub orientation: Option<OrientationConfig>,
+  pub sig_motion: Option<SigMotionConfig>,
+  pub tap: Option<TapConfig>,
+  pub tilt: Option<TiltConfig>,
+  /// Raw step-counter watermark (see [`Bmi323::set_step_watermark`]).
+  pub step_watermark: Option<u16>,
+  /// Complete enable mask written after the configurations.
+  pub enabled: Features,
+}
+
+impl Default for FeatureSettings {
+  fn default() -> Self {
+    Self {
+      axis_remap: None,
+      any_motion: None,
+      no_motion: None,
+      flat: None,
+      orientation: None,
+      sig_motion: None,
+      tap: None,
+      tilt: None,
+      step_watermark: None,
+      enabled: Features::none(),
     }
   }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 2)]
 pub struct FeatureDataStatus {
   pub out_of_bound_err: bool,
@@ -412,6 +713,7 @@ pub struct FeatureDataStatus {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 2)]
 pub struct FeatureEngineStatus {
   /// Feature engine is in halt or sleep state.
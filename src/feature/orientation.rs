@@ -8,7 +8,9 @@ where
   D: DelayNs,
 {
   pub async fn set_orientation_conf(&mut self, cfg: OrientationConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::Orient, cfg).await
+    self.write_feature(super::FeatureAddr::Orient, cfg).await?;
+    self.snapshot.orientation = Some(cfg);
+    Ok(())
   }
 
   pub async fn get_orientation_conf(&mut self) -> Result<OrientationConfig, Error<E>> {
@@ -21,6 +23,11 @@ where
     f.orientation = true;
     self.set_enabled_features(f).await
   }
+
+  /// Disable orientation detection.
+  pub async fn disable_orientation(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::ORIENTATION).await
+  }
 }
 
 /// Orientation (portrait/landscape, face‑up/down) configuration.
@@ -45,6 +52,7 @@ where
 /// - Hold time is quantized in 20 ms steps. Use small but non‑zero values to avoid boundary flicker.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 4)]
 pub struct OrientationConfig {
   /// Enable face‑up/face‑down classification (false: ignore face state).
@@ -72,12 +80,15 @@ pub struct OrientationConfig {
   /// - raw 10 → 200 ms
   /// - raw 25 → 500 ms (near the upper end for this 5‑bit field)
   #[bits(5)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms20::<5>"))]
   pub hold_time: u8,
   /// Slope threshold (LSB = 1/512 g). g ≈ raw/512. Default = 205 (≈400.365 mg).
   /// Examples: 1 → ≈0.002 g, 10 → ≈0.020 g, 50 → ≈0.098 g, 255 → ≈0.498 g.
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<8>"))]
   pub slope_threshold: u8,
   /// Slope hysteresis (LSB = 1/512 g). g ≈ raw/512.
   /// Examples: 1 → ≈0.002 g, 10 → ≈0.020 g, 50 → ≈0.098 g, 255 → ≈0.498 g.
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<8>"))]
   pub hysterisis: u8,
 }
 
@@ -117,6 +128,7 @@ impl Default for OrientationConfig {
 /// and exit), which helps reduce chatter near the decision boundary.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OrientationMode {
   Symmetric = 0,
   MildAsymmetric = 1,
@@ -8,7 +8,9 @@ where
   D: DelayNs,
 {
   pub async fn set_sign_motion_conf(&mut self, cfg: SigMotionConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::SigMotion, cfg).await
+    self.write_feature(super::FeatureAddr::SigMotion, cfg).await?;
+    self.snapshot.sig_motion = Some(cfg);
+    Ok(())
   }
 
   pub async fn get_sign_motion_conf(&mut self) -> Result<SigMotionConfig, Error<E>> {
@@ -21,6 +23,11 @@ where
     f.sig_motion = true;
     self.set_enabled_features(f).await
   }
+
+  /// Disable significant‑motion detection.
+  pub async fn disable_sig_motion(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::SIG_MOTION).await
+  }
 }
 
 /// Significant Motion configuration (raw feature fields).
@@ -42,18 +49,22 @@ where
 /// - Larger `block_size` smooths sporadic motion but increases detection latency.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 6)]
 pub struct SigMotionConfig {
   /// Block size (encoded) used by the internal estimator. LSB = 20 ms. Default = 0xFA (≈5 s).
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms20::<16>"))]
   pub block_size: u16,
   /// Minimum peak‑to‑peak amplitude (lower 10 bits, encoded). LSB ≈ 1.953 mg. Default = 0x26.
   #[bits(10)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<10>"))]
   pub p2p_min: u16,
   /// Minimum mean‑crossing rate (upper 6 bits, 0..63). Default = 17.
   #[bits(6)]
   pub mcr_min: u8,
   /// Maximum peak‑to‑peak amplitude (lower 10 bits, encoded). LSB ≈ 1.953 mg. Default = 0x253.
   #[bits(10)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<10>"))]
   pub p2p_max: u16,
   /// Maximum mean‑crossing rate (upper 6 bits, 0..63). Default = 17.
   #[bits(6)]
@@ -28,7 +28,15 @@ where
   pub async fn set_step_watermark(&mut self, wm: u16) -> Result<(), Error<E>> {
     let mut w0: StepWord0 = self.read_feature(super::FeatureAddr::StepCnt).await?;
     w0.watermark = core::cmp::min(wm, (1u16 << 10) - 1);
-    self.write_feature(super::FeatureAddr::StepCnt, w0).await
+    self.write_feature(super::FeatureAddr::StepCnt, w0).await?;
+    self.snapshot.step_watermark = Some(w0.watermark);
+    Ok(())
+  }
+
+  /// Read the raw step counter watermark (see [`set_step_watermark`](Self::set_step_watermark)).
+  pub async fn get_step_watermark(&mut self) -> Result<u16, Error<E>> {
+    let w0: StepWord0 = self.read_feature(super::FeatureAddr::StepCnt).await?;
+    Ok(w0.watermark)
   }
 
   /// Reset the step counter by setting the reset bit in word 0.
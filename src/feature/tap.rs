@@ -12,7 +12,9 @@ where
   /// - w1: peak threshold (10b) + max gesture duration (6b)
   /// - w2: timing nibbles (max_dur_bw_peaks, shock_sett, min_quiet, quiet_after)
   pub async fn set_tap_conf(&mut self, cfg: TapConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::Tap, cfg).await
+    self.write_feature(super::FeatureAddr::Tap, cfg).await?;
+    self.snapshot.tap = Some(cfg);
+    Ok(())
   }
 
   /// Read TAP configuration block.
@@ -28,6 +30,11 @@ where
     f.tap_triple = triple;
     self.set_enabled_features(f).await
   }
+
+  /// Disable single, double and triple tap detection.
+  pub async fn disable_tap(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::TAP).await
+  }
 }
 
 /// Tap detector configuration (raw feature fields).
@@ -59,6 +66,7 @@ where
 ///   `false` yields faster single‑tap reporting.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 6)]
 pub struct TapConfig {
   /// X/Y/Z selection for evaluation. Default = Z (2).
@@ -73,20 +81,27 @@ pub struct TapConfig {
   pub mode: TapMode,
   #[skip(8)]
   #[bits(10)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Mg::<10>"))]
   pub peak_thres: u16, // LSB ≈ 1.953 mg; Default ≈ 0x2D
   #[bits(6)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms40::<6>"))]
   pub max_gest_dur: u8, // LSB = 40 ms; Default = 16 (≈640 ms)
   #[bits(4)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms5::<4>"))]
   pub max_dur_bw_peaks: u8, // LSB = 5 ms; Default = 4 (≈20 ms)
   #[bits(4)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms5::<4>"))]
   pub shock_sett_dur: u8, // LSB = 5 ms; Default = 6 (≈30 ms)
   #[bits(4)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms5::<4>"))]
   pub min_quiet_dur_bw_taps: u8, // LSB = 5 ms; Default = 8 (≈40 ms)
   #[bits(4)]
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms40::<4>"))]
   pub quiet_time_after_gest: u8, // LSB = 40 ms; Default = 6 (≈240 ms)
 }
 
 impl TapConfig {
+  #[allow(clippy::too_many_arguments)]
   pub const fn new(
     axis: TapAxis,
     wait_for_timeout: bool,
@@ -135,6 +150,7 @@ impl Default for TapConfig {
 /// Axis selection for tap detection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TapAxis {
   X = 0,
   Y = 1,
@@ -170,6 +186,7 @@ impl TryFrom<u8> for TapAxis {
 /// for single/double/triple taps and environment.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TapMode {
   Mode0 = 0,
   Mode1 = 1,
@@ -8,7 +8,9 @@ where
   D: DelayNs,
 {
   pub async fn set_tilt_conf(&mut self, cfg: TiltConfig) -> Result<(), Error<E>> {
-    self.write_feature(super::FeatureAddr::Tilt, cfg).await
+    self.write_feature(super::FeatureAddr::Tilt, cfg).await?;
+    self.snapshot.tilt = Some(cfg);
+    Ok(())
   }
 
   pub async fn get_tilt_conf(&mut self) -> Result<TiltConfig, Error<E>> {
@@ -21,6 +23,11 @@ where
     f.tilt = true;
     self.set_enabled_features(f).await
   }
+
+  /// Disable tilt detection.
+  pub async fn disable_tilt(&mut self) -> Result<(), Error<E>> {
+    self.disable_features(super::Features::TILT).await
+  }
 }
 
 /// Tilt detector configuration (raw feature fields).
@@ -39,9 +46,11 @@ where
 /// - Excessive averaging or very low ODR can hide short tilts.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 4)]
 pub struct TiltConfig {
   /// Segment size (encoded, 8‑bit). LSB = 20 ms. Default = 100.
+  #[cfg_attr(feature = "serde", serde(with = "crate::units::Ms20::<8>"))]
   pub segment_size: u8,
   /// Minimum tilt angle (encoded). `raw = 256 * cos(angle)`; Default = 210.
   pub min_tilt_angle: u8,
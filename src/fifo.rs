@@ -11,8 +11,8 @@
 //!
 //! // Configure FIFO to store accelerometer and gyroscope data
 //! let fifo_config = FifoConfig {
-//!     acc_en: true,
-//!     gyr_en: true,
+//!     accel_en: true,
+//!     gyro_en: true,
 //!     stop_on_full: false,
 //!     ..Default::default()
 //! };
@@ -28,8 +28,9 @@
 //! ```
 
 use embedded_hal_async::{delay::DelayNs, i2c::*};
+use micromath::vector::Vector3d;
 
-use super::{defs::*, Bmi323, Error};
+use super::{axis_remap::Rotation, defs::*, Bmi323, Error};
 
 impl<I, D, W, E> Bmi323<I, D, W>
 where
@@ -43,7 +44,13 @@ where
   /// - Use `stop_on_full` for lossless burst capture; otherwise oldest data
   ///   is overwritten when full.
   pub async fn set_fifo_config(&mut self, cfg: FifoConfig) -> Result<(), Error<E>> {
-    self.write(Reg::FifoConf, cfg).await
+    self.write(Reg::FifoConf, cfg).await?;
+    self.snapshot.fifo = Some(cfg);
+    Ok(())
+  }
+
+  pub async fn get_fifo_config(&mut self) -> Result<FifoConfig, Error<E>> {
+    self.read(Reg::FifoConf).await
   }
 
   /// Set FIFO watermark level (in words). Triggers watermark interrupt if enabled.
@@ -53,7 +60,15 @@ where
   pub async fn set_fifo_watermark(&mut self, level_words: u16) -> Result<(), Error<E>> {
     let mut r = FifoWatermark { watermark: 0 };
     r.watermark = core::cmp::min(level_words, (1u16 << 10) - 1);
-    self.write(Reg::FifoWatermark, r).await
+    self.write(Reg::FifoWatermark, r).await?;
+    self.snapshot.fifo_watermark = Some(r.watermark);
+    Ok(())
+  }
+
+  /// Read the FIFO watermark level (in words).
+  pub async fn get_fifo_watermark(&mut self) -> Result<u16, Error<E>> {
+    let r: FifoWatermark = self.read(Reg::FifoWatermark).await?;
+    Ok(r.watermark)
   }
 
   /// Read current FIFO fill level (in words).
@@ -103,10 +118,114 @@ where
     }
     Ok(w)
   }
+
+  /// Split FIFO words read with [`read_fifo_words`](Self::read_fifo_words)
+  /// into frames.
+  ///
+  /// `cfg` must match the configuration the data was recorded with. Samples
+  /// are rotated by the [frame transform](Self::set_frame_transform) if one
+  /// is set, so FIFO and register data share the same frame.
+  pub fn parse_fifo<'a>(&self, cfg: FifoConfig, words: &'a [u16]) -> FifoFrames<'a> {
+    FifoFrames { rotation: self.frame, ..FifoFrames::new(cfg, words) }
+  }
+}
+
+/// Accelerometer word of a frame without a new accelerometer sample.
+const DUMMY_ACCEL: u16 = 0x7F01;
+/// Gyroscope word of a frame without a new gyroscope sample.
+const DUMMY_GYRO: u16 = 0x7F02;
+/// Temperature word of a frame without a new temperature sample.
+const DUMMY_TEMP: u16 = 0x8000;
+
+/// One FIFO frame. Sources that are disabled in [`FifoConfig`] or had no new
+/// sample for this frame are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FifoFrame {
+  /// Raw accelerometer sample.
+  pub accel: Option<Vector3d<i16>>,
+  /// Raw gyroscope sample.
+  pub gyro: Option<Vector3d<i16>>,
+  /// Raw temperature.
+  pub temp: Option<i16>,
+  /// Lower 16 bits of the sensor time.
+  pub time: Option<u16>,
+}
+
+/// Iterator over the frames in a block of FIFO words.
+///
+/// A frame holds, in order and only if enabled: accelerometer (3 words),
+/// gyroscope (3 words), temperature (1 word) and sensor time (1 word).
+/// A trailing partial frame is not yielded; see [`FifoFrames::remainder`].
+#[derive(Debug, Clone)]
+pub struct FifoFrames<'a> {
+  cfg: FifoConfig,
+  words: &'a [u16],
+  rotation: Option<Rotation>,
+}
+
+impl<'a> FifoFrames<'a> {
+  pub fn new(cfg: FifoConfig, words: &'a [u16]) -> Self {
+    Self { cfg, words, rotation: None }
+  }
+
+  /// Rotate accelerometer and gyroscope samples into the board frame.
+  pub fn with_rotation(self, rotation: Option<Rotation>) -> Self {
+    Self { rotation, ..self }
+  }
+
+  /// Words left after the last complete frame.
+  pub fn remainder(&self) -> &'a [u16] {
+    self.words
+  }
+
+  fn frame_len(&self) -> usize {
+    let c = &self.cfg;
+    3 * c.accel_en as usize + 3 * c.gyro_en as usize + c.temp_en as usize + c.time_en as usize
+  }
+
+  fn vector(&self, w: &[u16], dummy: u16) -> Option<Vector3d<i16>> {
+    if w[0] == dummy {
+      return None;
+    }
+    let v = Vector3d { x: w[0] as i16, y: w[1] as i16, z: w[2] as i16 };
+    Some(self.rotation.map_or(v, |r| r.apply_raw(v)))
+  }
+}
+
+impl Iterator for FifoFrames<'_> {
+  type Item = FifoFrame;
+
+  fn next(&mut self) -> Option<FifoFrame> {
+    let len = self.frame_len();
+    if len == 0 || self.words.len() < len {
+      return None;
+    }
+    let (mut w, rest) = self.words.split_at(len);
+    self.words = rest;
+
+    let mut frame = FifoFrame::default();
+    if self.cfg.accel_en {
+      frame.accel = self.vector(w, DUMMY_ACCEL);
+      w = &w[3..];
+    }
+    if self.cfg.gyro_en {
+      frame.gyro = self.vector(w, DUMMY_GYRO);
+      w = &w[3..];
+    }
+    if self.cfg.temp_en {
+      frame.temp = (w[0] != DUMMY_TEMP).then_some(w[0] as i16);
+      w = &w[1..];
+    }
+    if self.cfg.time_en {
+      frame.time = Some(w[0]);
+    }
+    Some(frame)
+  }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 2)]
 pub struct FifoConfig {
   #[bits(1)]
@@ -30,7 +30,7 @@
 use embedded_hal_async::{delay::DelayNs, i2c::*};
 use micromath::vector::Vector3d;
 
-use super::{defs::*, Bmi323, Error};
+use super::{defs::*, Bmi323, ConfigError, Error, Sensor};
 
 impl<I, D, W, E> Bmi323<I, D, W>
 where
@@ -45,11 +45,21 @@ where
   /// Set the gyroscope configuration.
   ///
   /// This configures the output data rate, measurement range, bandwidth,
-  /// averaging, and power mode. The function waits for the gyroscope
-  /// to be ready after applying the configuration.
+  /// averaging, and power mode. The configuration is checked with
+  /// [`GyroConfig::validate`] before writing, and `ERR_REG.gyr_conf_err`
+  /// afterwards ([`ConfigError::SensorConfRejected`]). Unless the gyroscope is
+  /// disabled or suspended, the function then waits for it to be ready.
   pub async fn set_gyro_conf(&mut self, cfg: GyroConfig) -> Result<(), Error<E>> {
+    cfg.validate().map_err(Error::Config)?;
     self.write(Reg::GyrConf, cfg).await?;
-    self.wait_for(crate::Sensor::Gyro).await
+    if self.get_error().await?.gyr_conf_err {
+      return Err(Error::Config(ConfigError::SensorConfRejected(Sensor::Gyro)));
+    }
+    if !matches!(cfg.mode, GyroPowerMode::Disable | GyroPowerMode::Suspend) {
+      self.wait_for(Sensor::Gyro).await?;
+    }
+    self.snapshot.gyro = Some(cfg);
+    Ok(())
   }
 
   /// Read raw gyroscope data (16-bit signed integers).
@@ -58,7 +68,7 @@ where
   /// to get scaled values in degrees per second.
   pub async fn get_raw_gyro_data(&mut self) -> Result<Vector3d<i16>, Error<E>> {
     let xyz: crate::XYZ = self.read(Reg::GyrDataX).await?;
-    Ok(Vector3d { x: xyz.x, y: xyz.y, z: xyz.z })
+    Ok(self.to_board_raw(Vector3d { x: xyz.x, y: xyz.y, z: xyz.z }))
   }
 
   /// Read gyroscope data scaled to degrees per second (°/s).
@@ -66,10 +76,10 @@ where
   /// Returns angular velocity in degrees per second for each axis.
   /// The scaling is automatically applied based on the configured range.
   pub async fn get_gyro_data(&mut self) -> Result<Vector3d<f32>, Error<E>> {
-    let accel_data = self.get_raw_gyro_data().await?;
+    let xyz: crate::XYZ = self.read(Reg::GyrDataX).await?;
     let range = self.get_gyro_conf().await?.range.multiplier();
 
-    Ok(Vector3d { x: accel_data.x as f32 * range, y: accel_data.y as f32 * range, z: accel_data.z as f32 * range })
+    Ok(self.to_board(Vector3d { x: xyz.x as f32 * range, y: xyz.y as f32 * range, z: xyz.z as f32 * range }))
   }
 }
 
@@ -79,6 +89,7 @@ where
 /// bandwidth, averaging, and power mode.
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 2)]
 pub struct GyroConfig {
   /// Output Data Rate in Hz (≈0.78 Hz to 6.4 kHz).
@@ -99,6 +110,23 @@ pub struct GyroConfig {
   pub mode: GyroPowerMode,
 }
 
+impl GyroConfig {
+  /// Check power mode, ODR and averaging against the datasheet rules.
+  ///
+  /// - Low-power mode: ODR ≤ 400 Hz and `ODR × averaged samples ≤ 400 Hz`.
+  /// - Normal and high-performance modes: ODR ≥ 12.5 Hz.
+  /// - A disabled or suspended gyroscope is always valid.
+  pub fn validate(&self) -> Result<(), ConfigError> {
+    match self.mode {
+      GyroPowerMode::Disable | GyroPowerMode::Suspend => Ok(()),
+      GyroPowerMode::LowPower => crate::types::check_odr_mode(Sensor::Gyro, true, self.odr, self.avg),
+      GyroPowerMode::Normal | GyroPowerMode::HighPerf => {
+        crate::types::check_odr_mode(Sensor::Gyro, false, self.odr, self.avg)
+      }
+    }
+  }
+}
+
 impl Default for GyroConfig {
   fn default() -> Self {
     GyroConfig {
@@ -114,6 +142,7 @@ impl Default for GyroConfig {
 /// Gyroscope measurement ranges
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GyroRange {
   /// ±125 degrees per second
   DPS125 = 0,
@@ -166,6 +195,7 @@ impl TryFrom<u8> for GyroRange {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GyroPowerMode {
   /// Gyroscope disabled
   Disable = 0x00,
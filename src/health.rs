@@ -0,0 +1,158 @@
+//! Error register decoding and feature-engine health monitoring.
+//!
+//! [`health_check`](crate::Bmi323::health_check) combines `ERR_REG`,
+//! `FEATURE_ENGINE_STATUS` and `FEATURE_IO1` into a single [`HealthReport`]
+//! with a suggested [`RecoveryAction`].
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::health::RecoveryAction;
+//!
+//! let report = imu.health_check().await.unwrap();
+//! match report.action {
+//!     RecoveryAction::None => {}
+//!     RecoveryAction::RetryLater => { /* back off and poll again */ }
+//!     RecoveryAction::RewriteSensorConfig => { /* re-apply ACC_CONF/GYR_CONF */ }
+//!     RecoveryAction::SoftReset => { /* soft reset and reconfigure */ }
+//! }
+//! # }
+//! ```
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::{Bmi323, Error, FeatureEngineState, FeatureEngineStatus, FeatureIo1, FeatureIoError};
+
+impl<I, D, W, E> Bmi323<I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Read all error/status sources and classify the device health.
+  pub async fn health_check(&mut self) -> Result<HealthReport, Error<E>> {
+    let errors = self.get_error().await?;
+    let engine = self.get_feature_engine_status().await?;
+    let io1 = self.get_feature_io1().await?;
+    Ok(HealthReport::new(errors, engine, io1))
+  }
+}
+
+/// Decoded `ERR_REG` (0x01).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[packbits::pack(bytes = 2)]
+pub struct ErrorRegister {
+  /// Fatal error; the chip is not operational. A soft reset or power-cycle is required.
+  pub fatal: bool,
+  /// Feature engine overloaded; some processing steps were skipped.
+  #[skip(1)]
+  pub feat_eng_overload: bool,
+  /// Feature engine watchdog expired. A soft reset is required.
+  #[skip(1)]
+  pub feat_eng_watchdog: bool,
+  /// `ACC_CONF` holds an invalid combination; the accelerometer kept its previous setting.
+  pub acc_conf_err: bool,
+  /// `GYR_CONF` holds an invalid combination; the gyroscope kept its previous setting.
+  pub gyr_conf_err: bool,
+  /// I3C SDR parity error.
+  #[skip(1)]
+  pub i3c_error0: bool,
+  /// I3C S0/S1 error.
+  #[skip(2)]
+  pub i3c_error3: bool,
+}
+
+impl ErrorRegister {
+  /// True when no error bit is set.
+  pub fn is_ok(&self) -> bool {
+    !(self.fatal
+      || self.feat_eng_overload
+      || self.feat_eng_watchdog
+      || self.acc_conf_err
+      || self.gyr_conf_err
+      || self.i3c_error0
+      || self.i3c_error3)
+  }
+}
+
+/// Suggested host reaction to a [`HealthReport`], ordered by severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecoveryAction {
+  /// Nothing to do.
+  None,
+  /// Transient condition (overload, ignored command, bus error); retry the last operation later.
+  RetryLater,
+  /// A sensor configuration was rejected; write a valid `ACC_CONF`/`GYR_CONF`.
+  RewriteSensorConfig,
+  /// The chip or its feature engine stopped; soft reset and reconfigure.
+  SoftReset,
+}
+
+/// Combined view of `ERR_REG`, `FEATURE_ENGINE_STATUS` and `FEATURE_IO1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HealthReport {
+  pub errors: ErrorRegister,
+  pub engine: FeatureEngineStatus,
+  pub engine_error: FeatureIoError,
+  pub engine_state: FeatureEngineState,
+  /// Most severe action required by any of the sources above.
+  pub action: RecoveryAction,
+}
+
+impl HealthReport {
+  pub(crate) fn new(errors: ErrorRegister, engine: FeatureEngineStatus, io1: FeatureIo1) -> Self {
+    let action = Self::action_for_errors(&errors)
+      .max(Self::action_for_engine(&engine))
+      .max(Self::action_for_io1(&io1));
+    Self { errors, engine, engine_error: io1.error_status, engine_state: io1.engine_state, action }
+  }
+
+  /// True when no source requires any action.
+  pub fn is_healthy(&self) -> bool {
+    self.action == RecoveryAction::None
+  }
+
+  fn action_for_errors(e: &ErrorRegister) -> RecoveryAction {
+    if e.fatal || e.feat_eng_watchdog {
+      RecoveryAction::SoftReset
+    } else if e.acc_conf_err || e.gyr_conf_err {
+      RecoveryAction::RewriteSensorConfig
+    } else if e.feat_eng_overload || e.i3c_error0 || e.i3c_error3 {
+      RecoveryAction::RetryLater
+    } else {
+      RecoveryAction::None
+    }
+  }
+
+  fn action_for_engine(s: &FeatureEngineStatus) -> RecoveryAction {
+    if s.watchdog_not_ack || s.disabled_by_host {
+      RecoveryAction::SoftReset
+    } else if s.overload {
+      RecoveryAction::RetryLater
+    } else {
+      RecoveryAction::None
+    }
+  }
+
+  fn action_for_io1(io1: &FeatureIo1) -> RecoveryAction {
+    use FeatureIoError::*;
+    if io1.engine_state == FeatureEngineState::ErrorMode {
+      return RecoveryAction::SoftReset;
+    }
+    match io1.error_status {
+      Inactive | Activated | NoError => RecoveryAction::None,
+      ConfigStringWrong => RecoveryAction::SoftReset,
+      AutoModeIllegalConfig | IllegalConfigWhileTcSync => RecoveryAction::RewriteSensorConfig,
+      AxisMapCmdNotProcessed
+      | I3cTcSyncError
+      | OngoingScOrStAborted
+      | ScCmdIgnored
+      | StCmdIgnored
+      | ScCmdNotProcessed
+      | TcSyncEnableWhileSt => RecoveryAction::RetryLater,
+    }
+  }
+}
@@ -0,0 +1,105 @@
+//! I3C timing-control (TC) synchronization.
+//!
+//! On an I3C bus the controller can distribute a time base to the sensor so
+//! that samples are taken in lock-step with the host. The time base is
+//! programmed into `I3C_TC_SYNC_TPH`/`TU`/`ODR`, applied with the
+//! `i3c_tcsync_update` command and activated through the feature engine.
+//!
+//! Sensor interrupts can be delivered in-band by mapping them to
+//! [`IntPin::Ibi`](crate::interrupt::IntPin::Ibi).
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::i3c::TcSyncConfig;
+//!
+//! imu.enable_feature_engine().await.unwrap();
+//! imu.enable_i3c_tc_sync(TcSyncConfig::new(1000, 10, 1)).await.unwrap();
+//! # }
+//! ```
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::{defs::*, Bmi323, ConfigError, Error, FeatureIoError};
+
+/// Time the feature engine needs to process `i3c_tcsync_update`.
+const TC_SYNC_UPDATE_DELAY_US: u32 = 1000;
+
+impl<I, D, W, E> Bmi323<I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Program the TC-sync time base and apply it with `i3c_tcsync_update`.
+  ///
+  /// Requires an enabled feature engine. Returns
+  /// [`ConfigError::Rejected`] if the engine reports a TC-sync error (e.g.
+  /// a self-test is running).
+  pub async fn set_i3c_tc_sync_conf(&mut self, cfg: TcSyncConfig) -> Result<(), Error<E>> {
+    self.write_u16(Reg::I3cTcSyncTph, cfg.tph).await?;
+    self.write_u16(Reg::I3cTcSyncTu, cfg.tu as u16).await?;
+    self.write_u16(Reg::I3cTcSyncOdr, cfg.odr as u16).await?;
+    self.write_u16(Reg::Cmd, Command::I3cTcSyncUpdate.into()).await?;
+    self.delay.delay_us(TC_SYNC_UPDATE_DELAY_US).await;
+    self.check_tc_sync_status().await?;
+    self.snapshot.i3c_tc_sync = Some(cfg);
+    Ok(())
+  }
+
+  /// Read back the programmed TC-sync time base.
+  pub async fn get_i3c_tc_sync_conf(&mut self) -> Result<TcSyncConfig, Error<E>> {
+    let tph = self.read_u16(Reg::I3cTcSyncTph).await?;
+    let tu = self.read_u16(Reg::I3cTcSyncTu).await? as u8;
+    let odr = self.read_u16(Reg::I3cTcSyncOdr).await? as u8;
+    Ok(TcSyncConfig { tph, tu, odr })
+  }
+
+  /// Program the time base and enable TC-sync (`FEATURE_IO0.i3c_sync`).
+  pub async fn enable_i3c_tc_sync(&mut self, cfg: TcSyncConfig) -> Result<(), Error<E>> {
+    self.set_i3c_tc_sync_conf(cfg).await?;
+    let mut f = self.get_enabled_features().await?;
+    f.i3c_sync = true;
+    self.set_enabled_features(f).await?;
+    self.check_tc_sync_status().await
+  }
+
+  /// Disable TC-sync; samples follow the internal ODR again.
+  pub async fn disable_i3c_tc_sync(&mut self) -> Result<(), Error<E>> {
+    let mut f = self.get_enabled_features().await?;
+    f.i3c_sync = false;
+    self.set_enabled_features(f).await
+  }
+
+  async fn check_tc_sync_status(&mut self) -> Result<(), Error<E>> {
+    match self.get_feature_io1().await?.error_status {
+      e @ (FeatureIoError::I3cTcSyncError
+      | FeatureIoError::TcSyncEnableWhileSt
+      | FeatureIoError::IllegalConfigWhileTcSync) => Err(Error::Config(ConfigError::Rejected(e))),
+      _ => Ok(()),
+    }
+  }
+}
+
+/// TC-sync time base (`I3C_TC_SYNC_TPH`, `I3C_TC_SYNC_TU`, `I3C_TC_SYNC_ODR`).
+///
+/// The values mirror the controller's `SETXTIME` parameters: the sync period
+/// is `tph` time units of `tu`, and `odr` selects how many samples are taken
+/// per period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TcSyncConfig {
+  /// Sync period in time units.
+  pub tph: u16,
+  /// Time unit.
+  pub tu: u8,
+  /// Output data rate within the sync period.
+  pub odr: u8,
+}
+
+impl TcSyncConfig {
+  pub const fn new(tph: u16, tu: u8, odr: u8) -> Self {
+    Self { tph, tu, odr }
+  }
+}
@@ -11,8 +11,8 @@
 //!
 //! // Configure INT1 as active-high push-pull
 //! let int1 = IntConfig {
-//!     level: IntLevel::ActiveHigh,
-//!     output: IntOutputMode::PushPull,
+//!     level: ActiveLevel::ActiveHigh,
+//!     output: OutputMode::PushPull,
 //!     enable: true,
 //! };
 //! imu.set_int_config(true, int1, IntConfig::default()).await.unwrap();
@@ -40,7 +40,13 @@ where
   D: DelayNs,
 {
   pub async fn set_int_map(&mut self, map: IntMap) -> Result<(), Error<E>> {
-    self.write(Reg::IntMap1, map).await
+    self.write(Reg::IntMap1, map).await?;
+    self.snapshot.int_map = Some(map);
+    Ok(())
+  }
+
+  pub async fn get_int_map(&mut self) -> Result<IntMap, Error<E>> {
+    self.read(Reg::IntMap1).await
   }
 
   /// Set the interrupt latch mode (mapped to `INT_CONF.latch`).
@@ -53,7 +59,15 @@ where
     // Read‑modify‑write the latch bit in INT_CONF using bitfields
     let mut r: IntConfReg = self.read(Reg::IntConf).await?;
     r.latch = latch;
-    self.write(Reg::IntConf, r).await
+    self.write(Reg::IntConf, r).await?;
+    self.snapshot.int_latch = Some(latch);
+    Ok(())
+  }
+
+  /// Read the interrupt latch mode (`INT_CONF.latch`).
+  pub async fn get_int_latch(&mut self) -> Result<bool, Error<E>> {
+    let r: IntConfReg = self.read(Reg::IntConf).await?;
+    Ok(r.latch)
   }
 
   pub async fn set_int_pins(&mut self, pin1: IntConfig, pin2: IntConfig) -> Result<(), Error<E>> {
@@ -64,7 +78,9 @@ where
     r.int2_level = pin2.level;
     r.int2_output = pin2.output;
     r.int2_enable = pin2.enable;
-    self.write(Reg::IoIntCtrl, r).await
+    self.write(Reg::IoIntCtrl, r).await?;
+    self.snapshot.int_pins = Some((pin1, pin2));
+    Ok(())
   }
 
   /// Convenience: set latch and pin configurations in one call.
@@ -75,9 +91,7 @@ where
 
   pub async fn get_int_config(&mut self) -> Result<(IntConfig, IntConfig), Error<E>> {
     let reg: IoIntCtrl = self.read(Reg::IoIntCtrl).await?;
-    let p1 = IntConfig { output: reg.int1_output, level: reg.int1_level, enable: reg.int1_enable };
-    let p2 = IntConfig { output: reg.int2_output, level: reg.int2_level, enable: reg.int2_enable };
-    Ok((p1, p2))
+    Ok(reg.pins())
   }
 
   /// Read `INT_STATUS_INT1` (clear-on-read).
@@ -99,6 +113,7 @@ where
 // Hardware-aligned bitfield spanning INT_MAP1..2 (4 bytes)
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 4)]
 pub struct IntMap {
   #[bits(2)]
@@ -134,7 +149,6 @@ pub struct IntMap {
   pub fifo_full: IntPin,
 }
 
-// TapEvent collapsed into simple count in FeatureEventExt::Tap
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum OrientationPl {
@@ -203,8 +217,34 @@ pub struct FeatureEventExt {
   pub t_tap: bool,
 }
 
+impl FeatureEventExt {
+  /// Tap gestures flagged in this snapshot, in single → double → triple order.
+  ///
+  /// The feature engine may flag more than one gesture at once (e.g. a single
+  /// tap that was later confirmed as part of a double tap).
+  pub fn taps(&self) -> impl Iterator<Item = TapKind> {
+    [
+      (self.s_tap, TapKind::Single),
+      (self.d_tap, TapKind::Double),
+      (self.t_tap, TapKind::Triple),
+    ]
+    .into_iter()
+    .filter_map(|(set, kind)| set.then_some(kind))
+  }
+}
+
+/// Tap gesture decoded from `FEATURE_EVENT_EXT`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TapKind {
+  Single,
+  Double,
+  Triple,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct IntConfig {
   pub output: OutputMode,
   pub level: ActiveLevel,
@@ -219,12 +259,14 @@ impl Default for IntConfig {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum IntPin {
   #[default]
   None = 0x0,
   Int1 = 0x1,
   Int2 = 0x2,
-  // Ibi = 0b11,
+  /// I3C in-band interrupt.
+  Ibi = 0x3,
 }
 
 impl From<IntPin> for u8 {
@@ -240,7 +282,7 @@ impl TryFrom<u8> for IntPin {
       0x0 => Ok(IntPin::None),
       0x1 => Ok(IntPin::Int1),
       0x2 => Ok(IntPin::Int2),
-      0x3 => Ok(IntPin::None), // treat IBI as None here
+      0x3 => Ok(IntPin::Ibi),
       _ => Err(()),
     }
   }
@@ -258,6 +300,7 @@ pub enum LatchMode {
 /// Configures the pin output mode (push-pull or open-drain).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OutputMode {
   PushPull = 0,
   OpenDrain = 1,
@@ -283,6 +326,7 @@ impl TryFrom<u8> for OutputMode {
 /// Represents the active level for interrupt pins.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ActiveLevel {
   ActiveLow = 0,
   ActiveHigh = 1,
@@ -308,7 +352,7 @@ impl TryFrom<u8> for ActiveLevel {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 2)]
-struct IntConfReg {
+pub(crate) struct IntConfReg {
   #[bits(1)]
   pub latch: bool,
 }
@@ -316,7 +360,7 @@ struct IntConfReg {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 2)]
-struct IoIntCtrl {
+pub(crate) struct IoIntCtrl {
   // INT1
   #[bits(1)]
   pub int1_level: ActiveLevel,
@@ -334,6 +378,25 @@ struct IoIntCtrl {
   pub int2_enable: bool,
 }
 
+impl IoIntCtrl {
+  pub(crate) fn from_pins(p1: IntConfig, p2: IntConfig) -> Self {
+    Self {
+      int1_level: p1.level,
+      int1_output: p1.output,
+      int1_enable: p1.enable,
+      int2_level: p2.level,
+      int2_output: p2.output,
+      int2_enable: p2.enable,
+    }
+  }
+
+  pub(crate) fn pins(self) -> (IntConfig, IntConfig) {
+    let p1 = IntConfig { output: self.int1_output, level: self.int1_level, enable: self.int1_enable };
+    let p2 = IntConfig { output: self.int2_output, level: self.int2_level, enable: self.int2_enable };
+    (p1, p2)
+  }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 2)]
@@ -7,48 +7,159 @@ where
   I: I2c<SevenBitAddress, Error = E>,
   D: DelayNs,
 {
-  /// Get/set I2C interface control register (raw).
-  pub async fn get_i2c_if(&mut self) -> Result<u8, Error<E>> {
-    self.read_u8(Reg::IoI2cIf).await
+  /// Get/set I2C interface control (`IO_I2C_IF`: watchdog enable and timeout).
+  pub async fn get_i2c_if(&mut self) -> Result<IoI2cIf, Error<E>> {
+    self.read(Reg::IoI2cIf).await
   }
 
-  pub async fn set_i2c_if(&mut self, v: u8) -> Result<(), Error<E>> {
-    self.write_bytes(Reg::IoI2cIf, &[v]).await
+  pub async fn set_i2c_if(&mut self, v: IoI2cIf) -> Result<(), Error<E>> {
+    self.write(Reg::IoI2cIf, v).await?;
+    self.snapshot.i2c_if = Some(v);
+    Ok(())
   }
 
-  /// Get/set SPI interface control register (raw).
-  pub async fn get_spi_if(&mut self) -> Result<u8, Error<E>> {
-    self.read_u8(Reg::IoSpiIf).await
+  /// Enable the I2C watchdog with the given timeout.
+  ///
+  /// The device then releases a bus it has held low for longer than
+  /// `timeout`, so a glitch or a host reset mid-transfer cannot hang the bus.
+  pub async fn enable_i2c_watchdog(&mut self, timeout: I2cWatchdogTimeout) -> Result<(), Error<E>> {
+    let cfg = IoI2cIf { watchdog_sel: timeout, watchdog_en: true };
+    self.set_i2c_if(cfg).await
   }
 
-  pub async fn set_spi_if(&mut self, v: u8) -> Result<(), Error<E>> {
-    self.write_bytes(Reg::IoSpiIf, &[v]).await
+  /// Disable the I2C watchdog.
+  pub async fn disable_i2c_watchdog(&mut self) -> Result<(), Error<E>> {
+    self.set_i2c_if(IoI2cIf::default()).await
   }
 
-  /// Get/set IO pad drive strength (raw).
-  pub async fn get_io_pad_strength(&mut self) -> Result<u8, Error<E>> {
-    self.read_u8(Reg::IoPadStrength).await
+  /// Get/set SPI interface control (`IO_SPI_IF`: 3-/4-wire mode).
+  pub async fn get_spi_if(&mut self) -> Result<IoSpiIf, Error<E>> {
+    self.read(Reg::IoSpiIf).await
   }
 
-  pub async fn set_io_pad_strength(&mut self, v: u8) -> Result<(), Error<E>> {
-    self.write_bytes(Reg::IoPadStrength, &[v]).await
+  pub async fn set_spi_if(&mut self, v: IoSpiIf) -> Result<(), Error<E>> {
+    self.write(Reg::IoSpiIf, v).await?;
+    self.snapshot.spi_if = Some(v);
+    Ok(())
   }
 
-  /// Get/set ODR deviation (raw).
-  pub async fn get_io_odr_deviation(&mut self) -> Result<u8, Error<E>> {
-    self.read_u8(Reg::IoOdrDeviation).await
+  /// Get/set interface pad drive strength (`IO_PAD_STRENGTH`).
+  pub async fn get_io_pad_strength(&mut self) -> Result<IoPadStrength, Error<E>> {
+    self.read(Reg::IoPadStrength).await
   }
 
-  pub async fn set_io_odr_deviation(&mut self, v: u8) -> Result<(), Error<E>> {
-    self.write_bytes(Reg::IoOdrDeviation, &[v]).await
+  pub async fn set_io_pad_strength(&mut self, v: IoPadStrength) -> Result<(), Error<E>> {
+    self.write(Reg::IoPadStrength, v).await?;
+    self.snapshot.pad_strength = Some(v);
+    Ok(())
   }
 
-  /// Get/set IO power-down control (raw).
-  pub async fn get_io_pdn_ctrl(&mut self) -> Result<u8, Error<E>> {
-    self.read_u8(Reg::IoPdnCtrl).await
+  /// Get/set ODR deviation trim (`IO_ODR_DEVIATION`).
+  pub async fn get_io_odr_deviation(&mut self) -> Result<IoOdrDeviation, Error<E>> {
+    self.read(Reg::IoOdrDeviation).await
   }
 
-  pub async fn set_io_pdn_ctrl(&mut self, v: u8) -> Result<(), Error<E>> {
-    self.write_bytes(Reg::IoPdnCtrl, &[v]).await
+  pub async fn set_io_odr_deviation(&mut self, v: IoOdrDeviation) -> Result<(), Error<E>> {
+    self.write(Reg::IoOdrDeviation, v).await?;
+    self.snapshot.odr_deviation = Some(v);
+    Ok(())
   }
+
+  /// Get/set IO power-down control (`IO_PDN_CTRL`).
+  pub async fn get_io_pdn_ctrl(&mut self) -> Result<IoPdnCtrl, Error<E>> {
+    self.read(Reg::IoPdnCtrl).await
+  }
+
+  pub async fn set_io_pdn_ctrl(&mut self, v: IoPdnCtrl) -> Result<(), Error<E>> {
+    self.write(Reg::IoPdnCtrl, v).await?;
+    self.snapshot.pdn_ctrl = Some(v);
+    Ok(())
+  }
+}
+
+/// `IO_I2C_IF`: I2C watchdog.
+///
+/// With the watchdog enabled, the device releases SDA if the bus is held low
+/// longer than the selected timeout (e.g. after a host reset mid-transfer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[packbits::pack(bytes = 2)]
+pub struct IoI2cIf {
+  #[bits(1)]
+  pub watchdog_sel: I2cWatchdogTimeout,
+  pub watchdog_en: bool,
+}
+
+/// I2C watchdog timeout selection (`IO_I2C_IF.watchdog_sel`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum I2cWatchdogTimeout {
+  #[default]
+  Ms1_25 = 0,
+  Ms40 = 1,
+}
+
+impl From<I2cWatchdogTimeout> for u8 {
+  fn from(value: I2cWatchdogTimeout) -> Self {
+    value as u8
+  }
+}
+
+impl TryFrom<u8> for I2cWatchdogTimeout {
+  type Error = ();
+  fn try_from(v: u8) -> Result<Self, Self::Error> {
+    match v {
+      0 => Ok(I2cWatchdogTimeout::Ms1_25),
+      1 => Ok(I2cWatchdogTimeout::Ms40),
+      _ => Err(()),
+    }
+  }
+}
+
+/// `IO_SPI_IF`: SPI wiring.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[packbits::pack(bytes = 2)]
+pub struct IoSpiIf {
+  /// `true`: 3-wire SPI (SDI/SDO shared), `false`: 4-wire SPI.
+  pub spi3: bool,
+}
+
+/// `IO_PAD_STRENGTH`: output drive strength of the interface pads.
+///
+/// The register only trims the serial interface pads. INT1/INT2 have no
+/// separate drive setting; their output stage is chosen in `IO_INT_CTRL`
+/// (see [`IntConfig`](crate::interrupt::IntConfig)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[packbits::pack(bytes = 2)]
+pub struct IoPadStrength {
+  /// Drive strength of the serial interface pads, 0 (weakest) ..= 7 (strongest).
+  #[bits(3)]
+  pub if_drv: u8,
+}
+
+/// `IO_ODR_DEVIATION`: trim for the internal oscillator, i.e. the ODR deviation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[packbits::pack(bytes = 2)]
+pub struct IoOdrDeviation {
+  /// 5-bit trim value.
+  #[bits(5)]
+  pub trim: u8,
+}
+
+/// `IO_PDN_CTRL`: power-down control of the IO pads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[packbits::pack(bytes = 2)]
+pub struct IoPdnCtrl {
+  /// Power down the analog front end of the pads (`anim_dis`).
+  pub analog_power_down: bool,
 }
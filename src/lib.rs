@@ -16,20 +16,29 @@
 //! - [`interrupt`]: Interrupt pin configuration and status
 //! - [`feature`]: Feature engine for advanced motion detection
 //! - [`calib`]: Calibration utilities
+//! - [`config`]: Whole-device configuration applied and verified in one call
+//! - [`health`]: Error register decoding and health monitoring
+//! - [`dump`]: Full register and feature-memory dump for bug reports
+//! - [`i3c`]: I3C TC-sync configuration
+//! - [`recovery`]: Configuration snapshot and automatic recovery
+//! - [`power`]: Power-mode presets, validation and current estimates
 //! - [`selftest`]: Self-test functionality
+//! - [`typestate`]: Optional compile-time state tracking on top of [`Bmi323`]
+//! - `testing`: In-memory register model for host tests (requires `testing` feature)
+//! - `trace`: I2C transaction recorder and replay transport (requires `trace` feature)
 //!
 //! ## Basic Usage
 //!
 //! ```no_run
-//! # async fn example() -> Result<(), bmi323::Error<()>> {
+//! # async fn example<I: embedded_hal_async::i2c::I2c, D: embedded_hal_async::delay::DelayNs>(i2c: I, delay: D) -> Result<(), bmi323::Error<I::Error>> {
 //! # use bmi323::{Bmi323, accel::AccelConfig};
-//! # let i2c = (); // Your I2C implementation
-//! # let delay = (); // Your delay implementation
-//! let mut imu = Bmi323::new(i2c, delay);
+//! # #[cfg(not(feature = "events"))]
+//! let mut imu: Bmi323<_, _> = Bmi323::new(i2c, delay);
+//! # #[cfg(feature = "events")]
+//! # let mut imu = Bmi323::new(i2c, delay, bmi323::Polling::from_millis(20));
 //!
-//! // Initialize and verify chip
-//! imu.soft_reset().await?;
-//! let chip_id = imu.get_id().await?;
+//! // Reset, verify chip ID and error state
+//! imu.init(false).await?;
 //!
 //! // Configure and read accelerometer
 //! imu.set_accel_conf(AccelConfig::default()).await?;
@@ -39,48 +48,46 @@
 //! ```
 
 use embedded_hal_async::{delay::DelayNs, i2c::*};
+use micromath::vector::Vector3d;
 
 pub mod accel;
 pub mod alt;
 pub mod calib;
+pub mod config;
 mod defs;
+pub mod dump;
+mod error;
 #[cfg(feature = "events")]
 mod events;
 mod feature;
 pub mod fifo;
 pub mod gyro;
+pub mod health;
+pub mod i3c;
 pub mod interrupt;
 pub mod io;
 pub mod offset;
+pub mod power;
+pub mod recovery;
 pub(crate) mod rw;
 pub mod selftest;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "trace")]
+pub mod trace;
 mod types;
+pub mod typestate;
+#[cfg(feature = "serde")]
+mod units;
 
+pub use defs::Reg;
 use defs::*;
+pub use error::{ConfigError, Error, Operation};
 #[cfg(feature = "events")]
 pub use events::*;
 pub use feature::*;
 pub use types::*;
 
-/// Driver error type.
-///
-/// This error type wraps the underlying I2C error and adds BMI323-specific
-/// error conditions.
-#[derive(Debug)]
-#[cfg_attr(feature = "defmt", derive(defmt::Format))]
-pub enum Error<E> {
-  /// I2C communication error
-  I2c(E),
-  /// Chip ID mismatch (expected 0x43)
-  InvalidChipId(u8),
-  /// Invalid mode or configuration
-  InvalidMode,
-  /// Initialization failed (e.g., feature engine activation timeout)
-  Init,
-  /// Data error (e.g., timeout waiting for data ready)
-  Data,
-}
-
 /// BMI323 device driver instance.
 ///
 /// This is the main entry point for interacting with the BMI323 sensor.
@@ -91,16 +98,19 @@ pub enum Error<E> {
 ///
 /// - `I`: I2C implementation (must implement `embedded_hal_async::i2c::I2c`)
 /// - `D`: Delay provider (must implement `embedded_hal_async::delay::DelayNs`)
-/// - `W`: Interrupt wait implementation (only used with `events` feature)
+/// - `W`: Event source (only used with `events` feature): an interrupt pin
+///   implementing `embedded_hal_async::digital::Wait`, or `Polling` for boards
+///   without a wired interrupt line
 ///
 /// # Examples
 ///
 /// ```no_run
-/// # async fn example() -> Result<(), bmi323::Error<()>> {
+/// # async fn example<I: embedded_hal_async::i2c::I2c, D: embedded_hal_async::delay::DelayNs>(i2c: I, delay: D) -> Result<(), bmi323::Error<I::Error>> {
 /// # use bmi323::Bmi323;
-/// # let i2c = (); // Your I2C implementation
-/// # let delay = (); // Your delay implementation
-/// let mut imu = Bmi323::new(i2c, delay);
+/// # #[cfg(not(feature = "events"))]
+/// let mut imu: Bmi323<_, _> = Bmi323::new(i2c, delay);
+/// # #[cfg(feature = "events")]
+/// # let mut imu = Bmi323::new(i2c, delay, bmi323::Polling::from_millis(20));
 /// imu.soft_reset().await?;
 /// # Ok(())
 /// # }
@@ -108,10 +118,13 @@ pub enum Error<E> {
 pub struct Bmi323<I, D: DelayNs, W = ()> {
   i2c: I,
   delay: D,
+  snapshot: recovery::ConfigSnapshot,
+  i2c_errors: u16,
+  frame: Option<axis_remap::Rotation>,
   #[cfg(feature = "events")]
   dequeue: heapless::Deque<Event, 16>,
   #[cfg(feature = "events")]
-  int_pin: W,
+  source: W,
   #[cfg(not(feature = "events"))]
   _wait: core::marker::PhantomData<W>,
 }
@@ -122,7 +135,7 @@ impl<I, D, W> Bmi323<I, D, W>
 where
   I: I2c<SevenBitAddress>,
   D: DelayNs,
-  W: embedded_hal_async::digital::Wait,
+  W: EventSource,
 {
   /// Create a new BMI323 driver instance with interrupt event support.
   ///
@@ -130,9 +143,25 @@ where
   ///
   /// - `i2c`: I2C bus implementation
   /// - `delay`: Delay provider for timing operations
-  /// - `int_pin`: Interrupt pin for event-driven operation (requires `events` feature)
-  pub fn new(i2c: I, delay: D, int_pin: W) -> Self {
-    Self { i2c, delay, dequeue: heapless::Deque::new(), int_pin }
+  /// - `source`: Interrupt pin for event-driven operation, or [`Polling`] to
+  ///   sample the status registers periodically (requires `events` feature)
+  pub fn new(i2c: I, delay: D, source: W) -> Self {
+    Self {
+      i2c,
+      delay,
+      snapshot: recovery::ConfigSnapshot::new(),
+      i2c_errors: 0,
+      frame: None,
+      dequeue: heapless::Deque::new(),
+      source,
+    }
+  }
+
+  /// Create a driver and run [`init`](Self::init), returning a verified, ready device.
+  pub async fn probe(i2c: I, delay: D, source: W, feature_engine: bool) -> Result<Self, Error<I::Error>> {
+    let mut imu = Self::new(i2c, delay, source);
+    imu.init(feature_engine).await?;
+    Ok(imu)
   }
 }
 
@@ -149,7 +178,30 @@ where
   /// - `i2c`: I2C bus implementation
   /// - `delay`: Delay provider for timing operations
   pub fn new(i2c: I, delay: D) -> Self {
-    Self { i2c, delay, _wait: core::marker::PhantomData }
+    Self {
+      i2c,
+      delay,
+      snapshot: recovery::ConfigSnapshot::new(),
+      i2c_errors: 0,
+      frame: None,
+      _wait: core::marker::PhantomData,
+    }
+  }
+
+  /// Create a driver and run [`init`](Self::init), returning a verified, ready device.
+  ///
+  /// # Example
+  ///
+  /// ```no_run
+  /// # async fn example<I: embedded_hal_async::i2c::I2c, D: embedded_hal_async::delay::DelayNs>(i2c: I, delay: D) -> Result<(), bmi323::Error<I::Error>> {
+  /// let mut imu: bmi323::Bmi323<_, _> = bmi323::Bmi323::probe(i2c, delay, true).await?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub async fn probe(i2c: I, delay: D, feature_engine: bool) -> Result<Self, Error<I::Error>> {
+    let mut imu = Self::new(i2c, delay);
+    imu.init(feature_engine).await?;
+    Ok(imu)
   }
 }
 
@@ -195,9 +247,48 @@ where
     Ok(())
   }
 
-  /// Read `ERR_REG` (raw bits per datasheet).
-  pub async fn get_error(&mut self) -> Result<u16, Error<E>> {
-    self.read_u16(Reg::Err).await
+  /// Bring the sensor into a known, verified state.
+  ///
+  /// Sequence:
+  /// - Dummy read of `CHIP_ID` (the first access after power-up may fail or
+  ///   return stale data; its result is ignored).
+  /// - Soft reset.
+  /// - Verify the chip ID ([`Error::InvalidChipId`] if it is not 0x43).
+  /// - Check `ERR_REG.fatal` and `STATUS.por_detected` ([`Error::Init`] if the
+  ///   chip reports a fatal error or did not complete its power-on reset).
+  /// - Enable the feature engine if `feature_engine` is set.
+  ///
+  /// The configuration snapshot used by [`recover`](Self::recover) is cleared.
+  pub async fn init(&mut self, feature_engine: bool) -> Result<(), Error<E>> {
+    let _ = self.read_u8(Reg::ChipId).await;
+    self.soft_reset().await?;
+    self.clear_snapshot();
+
+    let id = self.get_id().await?;
+    if id != BMI323_CHIP_ID {
+      return Err(Error::InvalidChipId(id));
+    }
+
+    if self.get_error().await?.fatal {
+      return Err(Error::Init);
+    }
+    let st: Status = self.read(Reg::Status).await?;
+    if !st.por_detected {
+      return Err(Error::Init);
+    }
+
+    if feature_engine {
+      self.enable_feature_engine().await?;
+    }
+    Ok(())
+  }
+
+  /// Read and decode `ERR_REG`.
+  ///
+  /// See [`health_check`](Self::health_check) for a combined view including
+  /// the feature engine status.
+  pub async fn get_error(&mut self) -> Result<health::ErrorRegister, Error<E>> {
+    self.read(Reg::Err).await
   }
 
   /// Wait until the selected sensor sets its data-ready bit.
@@ -205,7 +296,7 @@ where
     let mut retries = 0;
     while !self.is_ready(sensor).await? {
       if retries > 20 {
-        return Err(Error::Data);
+        return Err(Error::Timeout(Operation::DataReady(sensor)));
       }
       self.delay.delay_ms(2).await;
       retries += 1;
@@ -242,6 +333,33 @@ where
     Ok((b[2] as u32) << 16 | (b[1] as u32) << 8 | (b[0] as u32))
   }
 
+  /// Read accelerometer and gyroscope samples in one burst, so both belong to
+  /// the same sample period.
+  ///
+  /// Like the single-sensor getters, the samples are rotated by the
+  /// [frame transform](Self::set_frame_transform) if one is set.
+  pub async fn get_raw_accel_gyro_data(&mut self) -> Result<(Vector3d<i16>, Vector3d<i16>), Error<E>> {
+    let mut b = [0u8; 12];
+    // ACC_DATA_X..Z and GYR_DATA_X..Z are consecutive.
+    self.read_bytes(Reg::AccDataX, &mut b).await?;
+    let w = |i: usize| i16::from_le_bytes([b[2 * i], b[2 * i + 1]]);
+    let accel = Vector3d { x: w(0), y: w(1), z: w(2) };
+    let gyro = Vector3d { x: w(3), y: w(4), z: w(5) };
+    Ok((self.to_board_raw(accel), self.to_board_raw(gyro)))
+  }
+
+  /// Read accelerometer (g) and gyroscope (°/s) samples in one burst.
+  pub async fn get_accel_gyro_data(&mut self) -> Result<(Vector3d<f32>, Vector3d<f32>), Error<E>> {
+    let mut b = [0u8; 12];
+    self.read_bytes(Reg::AccDataX, &mut b).await?;
+    let a = self.get_accel_conf().await?.range.multiplier();
+    let g = self.get_gyro_conf().await?.range.multiplier();
+    let w = |i: usize| i16::from_le_bytes([b[2 * i], b[2 * i + 1]]) as f32;
+    let accel = Vector3d { x: w(0) * a, y: w(1) * a, z: w(2) * a };
+    let gyro = Vector3d { x: w(3) * g, y: w(4) * g, z: w(5) * g };
+    Ok((self.to_board(accel), self.to_board(gyro)))
+  }
+
   /// Read saturation flags for accel/gyro axes.
   pub async fn get_saturation_flags(&mut self) -> Result<SaturationFlags, Error<E>> {
     self.read(Reg::SatFlags).await
@@ -252,7 +370,8 @@ where
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 #[packbits::pack(bytes = 1)]
 struct Status {
-  #[skip(5)]
+  pub por_detected: bool,
+  #[skip(4)]
   pub drdy_temp: bool,
   pub drdy_gyr: bool,
   pub drdy_acc: bool,
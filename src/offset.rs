@@ -12,7 +12,9 @@ where
   }
 
   pub async fn set_accel_offset_gain(&mut self, v: AccelOffsetGain) -> Result<(), Error<E>> {
-    self.write(Reg::AccDpOffX, v).await
+    self.write(Reg::AccDpOffX, v).await?;
+    self.snapshot.accel_offset = Some(v);
+    Ok(())
   }
 
   pub async fn get_gyro_offset_gain(&mut self) -> Result<GyroOffsetGain, Error<E>> {
@@ -20,12 +22,15 @@ where
   }
 
   pub async fn set_gyro_offset_gain(&mut self, v: GyroOffsetGain) -> Result<(), Error<E>> {
-    self.write(Reg::GyrDpOffX, v).await
+    self.write(Reg::GyrDpOffX, v).await?;
+    self.snapshot.gyro_offset = Some(v);
+    Ok(())
   }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 12)]
 pub struct AccelOffsetGain {
   #[bits(14)]
@@ -49,6 +54,7 @@ pub struct AccelOffsetGain {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[packbits::pack(bytes = 12)]
 pub struct GyroOffsetGain {
   #[bits(10)]
@@ -0,0 +1,217 @@
+//! Power-mode presets and current estimates.
+//!
+//! Picking a legal combination of power mode, ODR and averaging for the
+//! accelerometer and gyroscope is error prone. [`PowerProfile`] provides
+//! consistent presets; [`PowerSettings`] lets you tweak a preset, checks it
+//! against the datasheet rules before anything is written, and estimates the
+//! resulting supply current.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::power::PowerProfile;
+//!
+//! let settings = PowerProfile::UltraLowPowerWakeOnMotion.settings();
+//! let current_ua = settings.estimated_current_ua();
+//! imu.apply_power_settings(settings).await.unwrap();
+//! # }
+//! ```
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::{
+  accel::{AccelConfig, AccelPowerMode, AccelRange},
+  gyro::{GyroConfig, GyroPowerMode, GyroRange},
+  AverageNum, Bandwidth, Bmi323, ConfigError, Error, Features, OutputDataRate,
+};
+
+impl<I, D, W, E> Bmi323<I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Apply one of the built-in power presets.
+  pub async fn apply_power_profile(&mut self, profile: PowerProfile) -> Result<(), Error<E>> {
+    self.apply_power_settings(profile.settings()).await
+  }
+
+  /// Validate and apply accelerometer, gyroscope and feature settings.
+  ///
+  /// Nothing is written if validation fails. When `settings.features` is set,
+  /// the feature engine is enabled first if needed and the feature enable mask
+  /// is replaced.
+  pub async fn apply_power_settings(&mut self, settings: PowerSettings) -> Result<(), Error<E>> {
+    settings.validate().map_err(Error::Config)?;
+
+    if let Some(features) = settings.features {
+      if !self.is_feature_engine_enabled().await? {
+        self.enable_feature_engine().await?;
+      }
+      self.set_enabled_features(features).await?;
+    }
+
+    self.set_accel_conf(settings.accel).await?;
+    self.set_gyro_conf(settings.gyro).await
+  }
+}
+
+/// Built-in power presets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PowerProfile {
+  /// Accelerometer in low-power mode at 50 Hz (2× averaging), gyroscope off,
+  /// any-motion enabled on all axes to wake the host.
+  UltraLowPowerWakeOnMotion,
+  /// Accelerometer in low-power mode at 50 Hz (4× averaging), gyroscope off,
+  /// step detector and step counter enabled.
+  LowPowerStepCounting,
+  /// Accelerometer and gyroscope in normal mode at 100 Hz.
+  Normal,
+  /// Accelerometer and gyroscope in high-performance mode at 800 Hz.
+  HighPerformance,
+}
+
+impl PowerProfile {
+  /// Register-level settings for this preset.
+  pub fn settings(self) -> PowerSettings {
+    let gyro_off = GyroConfig { mode: GyroPowerMode::Disable, ..GyroConfig::default() };
+    match self {
+      PowerProfile::UltraLowPowerWakeOnMotion => {
+        let mut features = Features::none();
+        features.any_motion_x = true;
+        features.any_motion_y = true;
+        features.any_motion_z = true;
+        PowerSettings {
+          accel: AccelConfig {
+            odr: OutputDataRate::Hz50,
+            range: AccelRange::G8,
+            bw: Bandwidth::OdrHalf,
+            avg: AverageNum::Avg2,
+            mode: AccelPowerMode::LowPower,
+          },
+          gyro: gyro_off,
+          features: Some(features),
+        }
+      }
+      PowerProfile::LowPowerStepCounting => {
+        let mut features = Features::none();
+        features.step_detector = true;
+        features.step_counter = true;
+        PowerSettings {
+          accel: AccelConfig {
+            odr: OutputDataRate::Hz50,
+            range: AccelRange::G8,
+            bw: Bandwidth::OdrHalf,
+            avg: AverageNum::Avg4,
+            mode: AccelPowerMode::LowPower,
+          },
+          gyro: gyro_off,
+          features: Some(features),
+        }
+      }
+      PowerProfile::Normal => PowerSettings {
+        accel: AccelConfig {
+          odr: OutputDataRate::Hz100,
+          range: AccelRange::G8,
+          bw: Bandwidth::OdrHalf,
+          avg: AverageNum::No,
+          mode: AccelPowerMode::Normal,
+        },
+        gyro: GyroConfig {
+          odr: OutputDataRate::Hz100,
+          range: GyroRange::DPS2000,
+          bw: Bandwidth::OdrHalf,
+          avg: AverageNum::No,
+          mode: GyroPowerMode::Normal,
+        },
+        features: None,
+      },
+      PowerProfile::HighPerformance => PowerSettings {
+        accel: AccelConfig {
+          odr: OutputDataRate::Hz800,
+          range: AccelRange::G8,
+          bw: Bandwidth::OdrHalf,
+          avg: AverageNum::No,
+          mode: AccelPowerMode::HighPerf,
+        },
+        gyro: GyroConfig {
+          odr: OutputDataRate::Hz800,
+          range: GyroRange::DPS2000,
+          bw: Bandwidth::OdrHalf,
+          avg: AverageNum::No,
+          mode: GyroPowerMode::HighPerf,
+        },
+        features: None,
+      },
+    }
+  }
+}
+
+/// Sensor and feature settings applied together by [`Bmi323::apply_power_settings`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerSettings {
+  pub accel: AccelConfig,
+  pub gyro: GyroConfig,
+  /// Feature enable mask to apply (`None`: leave the current mask untouched).
+  pub features: Option<Features>,
+}
+
+// Typical supply currents in µA (VDD = 1.8 V, 25 °C). Low-power modes are
+// duty-cycled, so their cost is modelled as a floor plus a per-sample charge.
+const CHIP_IDLE_UA: f32 = 3.5;
+const ACC_LP_FLOOR_UA: f32 = 2.5;
+const ACC_LP_PER_SAMPLE_UA: f32 = 0.1;
+const ACC_NORMAL_UA: f32 = 160.0;
+const ACC_HIGH_PERF_UA: f32 = 180.0;
+const GYR_SUSPEND_UA: f32 = 25.0;
+const GYR_LP_FLOOR_UA: f32 = 25.0;
+const GYR_LP_PER_SAMPLE_UA: f32 = 0.6;
+const GYR_NORMAL_UA: f32 = 420.0;
+const GYR_HIGH_PERF_UA: f32 = 610.0;
+const FEATURE_ENGINE_UA: f32 = 5.0;
+
+impl PowerSettings {
+  /// Check both sensor configurations (see [`AccelConfig::validate`] and
+  /// [`GyroConfig::validate`]).
+  pub fn validate(&self) -> Result<(), ConfigError> {
+    self.accel.validate()?;
+    self.gyro.validate()
+  }
+
+  /// Rough supply current estimate in µA from typical datasheet figures.
+  ///
+  /// Intended for comparing configurations and budgeting; actual current
+  /// depends on supply voltage, temperature, bus traffic and part spread.
+  pub fn estimated_current_ua(&self) -> f32 {
+    let accel = match self.accel.mode {
+      AccelPowerMode::Disable => 0.0,
+      AccelPowerMode::LowPower => {
+        ACC_LP_FLOOR_UA + ACC_LP_PER_SAMPLE_UA * samples_per_second(self.accel.odr, self.accel.avg)
+      }
+      AccelPowerMode::Normal => ACC_NORMAL_UA,
+      AccelPowerMode::HighPerf => ACC_HIGH_PERF_UA,
+    };
+    let gyro = match self.gyro.mode {
+      GyroPowerMode::Disable => 0.0,
+      GyroPowerMode::Suspend => GYR_SUSPEND_UA,
+      GyroPowerMode::LowPower => {
+        GYR_LP_FLOOR_UA + GYR_LP_PER_SAMPLE_UA * samples_per_second(self.gyro.odr, self.gyro.avg)
+      }
+      GyroPowerMode::Normal => GYR_NORMAL_UA,
+      GyroPowerMode::HighPerf => GYR_HIGH_PERF_UA,
+    };
+    let features = match self.features {
+      Some(f) if f != Features::none() => FEATURE_ENGINE_UA,
+      _ => 0.0,
+    };
+    CHIP_IDLE_UA + accel + gyro + features
+  }
+}
+
+fn samples_per_second(odr: OutputDataRate, avg: AverageNum) -> f32 {
+  odr.hz() * avg.samples() as f32
+}
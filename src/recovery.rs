@@ -0,0 +1,291 @@
+//! Self-healing after feature-engine watchdog and fatal errors.
+//!
+//! The driver remembers the last configuration written through its setters in
+//! a [`ConfigSnapshot`]. When [`health_check`](crate::Bmi323::health_check)
+//! asks for [`RecoveryAction::SoftReset`](crate::health::RecoveryAction::SoftReset),
+//! [`recover`](crate::Bmi323::recover) resets the chip and replays that
+//! snapshot so a long-running device returns to its previous state.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::health::RecoveryAction;
+//!
+//! if imu.health_check().await.unwrap().action == RecoveryAction::SoftReset {
+//!     imu.recover().await.unwrap();
+//! }
+//! # }
+//! ```
+//!
+//! After a run of bus errors, [`recover_bus`](crate::Bmi323::recover_bus)
+//! re-probes the chip and restores the configuration:
+//!
+//! ```no_run
+//! # async fn example(mut imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! if imu.get_accel_data().await.is_err() && imu.consecutive_i2c_errors() >= 3 {
+//!     imu.recover_bus(5).await.unwrap();
+//! }
+//! # }
+//! ```
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::{
+  accel::AccelConfig,
+  alt::{AltAutoConfig, AltEnable},
+  any_no_motion::AnyNoMotionConfig,
+  axis_remap::AxisRemap,
+  defs::BMI323_CHIP_ID,
+  fifo::FifoConfig,
+  flat::FlatConfig,
+  gyro::GyroConfig,
+  i3c::TcSyncConfig,
+  interrupt::IntConfig,
+  interrupt::IntMap,
+  io::{IoI2cIf, IoOdrDeviation, IoPadStrength, IoPdnCtrl, IoSpiIf},
+  offset::{AccelOffsetGain, GyroOffsetGain},
+  orientation::OrientationConfig,
+  sig_motion::SigMotionConfig,
+  tap::TapConfig,
+  tilt::TiltConfig,
+  Bmi323, Error, Features,
+};
+
+/// Pause between chip-ID probes in [`Bmi323::recover_bus`]; longer than the
+/// 40 ms I2C watchdog timeout.
+const BUS_RECOVERY_DELAY_MS: u32 = 50;
+
+impl<I, D, W, E> Bmi323<I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Configuration applied through this driver instance since creation.
+  pub fn snapshot(&self) -> &ConfigSnapshot {
+    &self.snapshot
+  }
+
+  /// Forget the recorded configuration; a later [`recover`](Self::recover) only resets the chip.
+  pub fn clear_snapshot(&mut self) {
+    self.snapshot = ConfigSnapshot::new();
+  }
+
+  /// Re-establish communication after repeated bus errors, then [`recover`](Self::recover).
+  ///
+  /// Probes the chip ID up to `attempts` times (at least once), waiting longer
+  /// than the maximum I2C watchdog timeout between tries so a device holding
+  /// the bus can release it. Once the chip answers with the expected ID, it is
+  /// soft-reset and the recorded configuration is replayed.
+  ///
+  /// Returns the last bus error if the chip never answers, or
+  /// [`Error::InvalidChipId`] if something else does.
+  pub async fn recover_bus(&mut self, attempts: u8) -> Result<(), Error<E>> {
+    let mut attempt = 1;
+    loop {
+      match self.get_id().await {
+        Ok(BMI323_CHIP_ID) => break,
+        Ok(id) => return Err(Error::InvalidChipId(id)),
+        Err(Error::I2c(_)) if attempt < attempts => {
+          self.delay.delay_ms(BUS_RECOVERY_DELAY_MS).await;
+          attempt += 1;
+        }
+        Err(e) => return Err(e),
+      }
+    }
+    self.recover().await
+  }
+
+  /// Soft-reset the chip and replay the recorded configuration.
+  ///
+  /// Sequence:
+  /// - Soft reset (all registers and feature memory return to defaults).
+  /// - I2C interface (watchdog), SPI wiring, pad drive strength, ODR trim and IO power-down settings.
+  /// - Re-enable the feature engine if it was enabled.
+  /// - Axis remap (must precede sensor enable), feature configs and enable mask.
+  /// - Data-path offsets and gains.
+  /// - Accelerometer and gyroscope configuration, then the alternate configurations.
+  /// - FIFO configuration and watermark.
+  /// - Interrupt pins, latch mode and map.
+  ///
+  /// Settings never written through the driver are left at their reset values.
+  pub async fn recover(&mut self) -> Result<(), Error<E>> {
+    let snap = self.snapshot;
+    self.soft_reset().await?;
+    if let Some(v) = snap.i2c_if {
+      self.set_i2c_if(v).await?;
+    }
+    if let Some(v) = snap.spi_if {
+      self.set_spi_if(v).await?;
+    }
+    if let Some(v) = snap.pad_strength {
+      self.set_io_pad_strength(v).await?;
+    }
+    if let Some(v) = snap.odr_deviation {
+      self.set_io_odr_deviation(v).await?;
+    }
+    if let Some(v) = snap.pdn_ctrl {
+      self.set_io_pdn_ctrl(v).await?;
+    }
+
+    if snap.feature_engine {
+      self.enable_feature_engine().await?;
+      if let Some(v) = snap.axis_remap {
+        self.set_axis_remap(v).await?;
+      }
+      if let Some(v) = snap.any_motion {
+        self.set_any_motion_conf(v).await?;
+      }
+      if let Some(v) = snap.no_motion {
+        self.set_no_motion_conf(v).await?;
+      }
+      if let Some(v) = snap.flat {
+        self.set_flat_conf(v).await?;
+      }
+      if let Some(v) = snap.orientation {
+        self.set_orientation_conf(v).await?;
+      }
+      if let Some(v) = snap.sig_motion {
+        self.set_sign_motion_conf(v).await?;
+      }
+      if let Some(v) = snap.tap {
+        self.set_tap_conf(v).await?;
+      }
+      if let Some(v) = snap.tilt {
+        self.set_tilt_conf(v).await?;
+      }
+      if let Some(v) = snap.i3c_tc_sync {
+        self.set_i3c_tc_sync_conf(v).await?;
+      }
+      if let Some(v) = snap.alt_auto {
+        self.set_alt_auto_conf(v).await?;
+      }
+      if let Some(v) = snap.step_watermark {
+        self.set_step_watermark(v).await?;
+      }
+      if let Some(v) = snap.features {
+        self.set_enabled_features(v).await?;
+      }
+    }
+
+    if let Some(v) = snap.accel_offset {
+      self.set_accel_offset_gain(v).await?;
+    }
+    if let Some(v) = snap.gyro_offset {
+      self.set_gyro_offset_gain(v).await?;
+    }
+    if let Some(v) = snap.accel {
+      self.set_accel_conf(v).await?;
+    }
+    if let Some(v) = snap.gyro {
+      self.set_gyro_conf(v).await?;
+    }
+    if let Some(v) = snap.alt_accel {
+      self.set_alt_accel_conf(v).await?;
+    }
+    if let Some(v) = snap.alt_gyro {
+      self.set_alt_gyro_conf(v).await?;
+    }
+    if let Some(v) = snap.alt_enable {
+      self.set_alt_enable(v).await?;
+    }
+
+    if let Some(v) = snap.fifo {
+      self.set_fifo_config(v).await?;
+    }
+    if let Some(v) = snap.fifo_watermark {
+      self.set_fifo_watermark(v).await?;
+    }
+
+    if let Some((p1, p2)) = snap.int_pins {
+      self.set_int_pins(p1, p2).await?;
+    }
+    if let Some(v) = snap.int_latch {
+      self.set_int_latch(v).await?;
+    }
+    if let Some(v) = snap.int_map {
+      self.set_int_map(v).await?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Last configuration written through the driver (`None` = never written).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct ConfigSnapshot {
+  pub feature_engine: bool,
+  pub i2c_if: Option<IoI2cIf>,
+  pub spi_if: Option<IoSpiIf>,
+  pub pad_strength: Option<IoPadStrength>,
+  pub odr_deviation: Option<IoOdrDeviation>,
+  pub pdn_ctrl: Option<IoPdnCtrl>,
+  pub accel_offset: Option<AccelOffsetGain>,
+  pub gyro_offset: Option<GyroOffsetGain>,
+  pub accel: Option<AccelConfig>,
+  pub gyro: Option<GyroConfig>,
+  pub alt_accel: Option<AccelConfig>,
+  pub alt_gyro: Option<GyroConfig>,
+  pub alt_enable: Option<AltEnable>,
+  pub alt_auto: Option<AltAutoConfig>,
+  pub fifo: Option<FifoConfig>,
+  pub fifo_watermark: Option<u16>,
+  pub int_pins: Option<(IntConfig, IntConfig)>,
+  pub int_latch: Option<bool>,
+  pub int_map: Option<IntMap>,
+  pub features: Option<Features>,
+  pub axis_remap: Option<AxisRemap>,
+  pub any_motion: Option<AnyNoMotionConfig>,
+  pub no_motion: Option<AnyNoMotionConfig>,
+  pub flat: Option<FlatConfig>,
+  pub orientation: Option<OrientationConfig>,
+  pub sig_motion: Option<SigMotionConfig>,
+  pub tap: Option<TapConfig>,
+  pub tilt: Option<TiltConfig>,
+  pub step_watermark: Option<u16>,
+  pub i3c_tc_sync: Option<TcSyncConfig>,
+}
+
+impl ConfigSnapshot {
+  pub const fn new() -> Self {
+    Self {
+      feature_engine: false,
+      i2c_if: None,
+      spi_if: None,
+      pad_strength: None,
+      odr_deviation: None,
+      pdn_ctrl: None,
+      accel_offset: None,
+      gyro_offset: None,
+      accel: None,
+      gyro: None,
+      alt_accel: None,
+      alt_gyro: None,
+      alt_enable: None,
+      alt_auto: None,
+      fifo: None,
+      fifo_watermark: None,
+      int_pins: None,
+      int_latch: None,
+      int_map: None,
+      features: None,
+      axis_remap: None,
+      any_motion: None,
+      no_motion: None,
+      flat: None,
+      orientation: None,
+      sig_motion: None,
+      tap: None,
+      tilt: None,
+      step_watermark: None,
+      i3c_tc_sync: None,
+    }
+  }
+}
+
+impl Default for ConfigSnapshot {
+  fn default() -> Self {
+    Self::new()
+  }
+}
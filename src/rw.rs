@@ -1,6 +1,9 @@
 use embedded_hal_async::{delay::DelayNs, i2c::*};
 
-use crate::{defs::Reg, defs::*, Bmi323, Error};
+use crate::{defs::*, Bmi323, Error};
+
+/// Largest payload read in one I2C transfer (plus two dummy bytes).
+const MAX_READ_BURST: usize = 30;
 
 impl<I, D, W, E> Bmi323<I, D, W>
 where
@@ -10,7 +13,7 @@ where
   pub(crate) async fn read<const N: usize, T: TryFrom<[u8; N]>>(&mut self, reg: Reg) -> Result<T, Error<E>> {
     let mut b = [0u8; N];
     self.read_bytes(reg, &mut b).await?;
-    TryFrom::try_from(b).map_err(|_| Error::Data)
+    TryFrom::try_from(b).map_err(|_| Error::InvalidRegisterValue(reg))
   }
 
   pub(crate) async fn read_u8(&mut self, reg: Reg) -> Result<u8, Error<E>> {
@@ -28,23 +31,27 @@ where
   pub(crate) async fn read_bytes(&mut self, reg: Reg, buf: &mut [u8]) -> Result<(), Error<E>> {
     // The device returns two dummy bytes before the actual register data.
     // Read into a temporary buffer and skip the first two bytes.
-    debug_assert!(buf.len() <= 30, "read_bytes buffer too large");
-
-    let mut tmp = [0u8; 32];
-    let read_len = buf.len() + 2; // two dummy bytes in front
-
-    self
-      .i2c
-      .write_read(ADDR_I2C_PRIM, &[reg as u8], &mut tmp[..read_len])
-      .await
-      .map_err(Error::I2c)?;
+    //
+    // Longer reads are split into bursts. That is only meaningful for the data
+    // ports (FIFO_DATA, FEATURE_DATA_TX), which do not advance the address;
+    // any other register would return the same bytes for every burst.
+    if buf.len() > MAX_READ_BURST && !matches!(reg, Reg::FifoData | Reg::FeatureDataTx) {
+      return Err(Error::InvalidLength(buf.len()));
+    }
 
-    buf.copy_from_slice(&tmp[2..read_len]);
+    let mut tmp = [0u8; MAX_READ_BURST + 2];
+    for chunk in buf.chunks_mut(MAX_READ_BURST) {
+      let read_len = chunk.len() + 2; // two dummy bytes in front
+      let out = &mut tmp[..read_len];
+      let res = self.i2c.write_read(ADDR_I2C_PRIM, &[reg as u8], out).await;
+      self.track_bus(res)?;
+      chunk.copy_from_slice(&tmp[2..read_len]);
+    }
     Ok(())
   }
 
   pub(crate) async fn write<const N: usize, T: TryInto<[u8; N]>>(&mut self, reg: Reg, v: T) -> Result<(), Error<E>> {
-    let b = v.try_into().map_err(|_| Error::Data)?;
+    let b = v.try_into().map_err(|_| Error::InvalidRegisterValue(reg))?;
     self.write_bytes(reg, &b).await
   }
 
@@ -59,8 +66,43 @@ where
     let len = 1 + data.len();
     buf[0] = reg as u8;
     buf[1..len].copy_from_slice(data);
-    self.i2c.write(ADDR_I2C_PRIM, &buf[..len]).await.map_err(Error::I2c)?;
+    let res = self.i2c.write(ADDR_I2C_PRIM, &buf[..len]).await;
+    self.track_bus(res)?;
     self.delay.delay_us(20).await;
     Ok(())
   }
+
+  /// Read the raw 16-bit value of any register.
+  ///
+  /// Intended for inspection tools; prefer the typed getters otherwise.
+  /// Reading a status register clears it just like the typed getters do.
+  pub async fn read_register(&mut self, reg: Reg) -> Result<u16, Error<E>> {
+    self.read_u16(reg).await
+  }
+
+  /// Write a raw 16-bit value to any register.
+  ///
+  /// Bypasses validation and the configuration snapshot, so [`recover`](Self::recover)
+  /// will not restore it.
+  pub async fn write_register(&mut self, reg: Reg, value: u16) -> Result<(), Error<E>> {
+    self.write_u16(reg, value).await
+  }
+
+  /// Number of I2C transfers that failed in a row (reset by any successful transfer).
+  pub fn consecutive_i2c_errors(&self) -> u16 {
+    self.i2c_errors
+  }
+
+  fn track_bus(&mut self, res: Result<(), E>) -> Result<(), Error<E>> {
+    match res {
+      Ok(()) => {
+        self.i2c_errors = 0;
+        Ok(())
+      }
+      Err(e) => {
+        self.i2c_errors = self.i2c_errors.saturating_add(1);
+        Err(Error::I2c(e))
+      }
+    }
+  }
 }
@@ -0,0 +1,500 @@
+//! In-memory BMI323 register model for host-side tests (`testing` feature).
+//!
+//! [`MockBmi323`] implements [`embedded_hal_async::i2c::I2c`] and behaves like
+//! the chip at the register level:
+//!
+//! - Every read returns the two dummy bytes in front of the data.
+//! - Multi-word accesses advance the register address; `FIFO_DATA` and
+//!   `FEATURE_DATA_TX` are ports that stay on the same register.
+//! - `FEATURE_DATA_ADDR`/`FEATURE_DATA_TX` reach a feature memory with address
+//!   auto-increment; `FEATURE_DATA_STATUS.data_tx_ready` is set while the
+//!   feature engine is enabled.
+//! - `INT_STATUS_INT1`/`INT2`, `FEATURE_EVENT_EXT` and `STATUS.por_detected`
+//!   clear on read; the `STATUS` data-ready bits follow the sensor power modes.
+//! - `CMD` handles soft reset, axis-map update (refused while a sensor is
+//!   active) and self-test; setting
+//!   `FEATURE_CTRL.engine_en` activates the feature engine.
+//! - The FIFO holds 1024 words and `FIFO_CTRL.flush` empties it.
+//!
+//! Test code injects stimuli (sample data, FIFO words, interrupt status, bus
+//! failures) and inspects registers through `&MockBmi323` while the driver owns
+//! another shared reference.
+//!
+//! # Examples
+//!
+//! ```
+//! use bmi323::testing::{block_on, MockBmi323, NoopDelay};
+//! # #[cfg(not(feature = "events"))]
+//! # {
+//! let mock = MockBmi323::new();
+//! let mut imu: bmi323::Bmi323<_, _> = bmi323::Bmi323::new(&mock, NoopDelay);
+//!
+//! block_on(imu.init(false)).unwrap();
+//! mock.set_accel_raw([100, -200, 4096]);
+//! assert_eq!(block_on(imu.get_raw_accel_data()).unwrap().z, 4096);
+//! # }
+//! ```
+
+use core::{
+  cell::RefCell,
+  future::Future,
+  pin::pin,
+  task::{Context, Poll, Waker},
+};
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+
+use crate::defs::{Command, Reg, ADDR_I2C_PRIM, BMI323_CHIP_ID};
+
+pub mod sim;
+
+/// Number of 16-bit registers in the primary register map.
+const REG_COUNT: usize = 0x80;
+/// Size of the extended (feature) memory in words.
+pub const FEATURE_WORDS: usize = 0x800;
+/// FIFO capacity in words (2 KiB).
+pub const FIFO_WORDS: usize = 1024;
+
+const ACC_DATA_Y: u8 = 0x04;
+const ACC_DATA_Z: u8 = 0x05;
+const GYR_DATA_Y: u8 = 0x07;
+const GYR_DATA_Z: u8 = 0x08;
+const INT_STATUS_IBI: u8 = 0x0F;
+
+const STATUS_POR: u16 = 1 << 0;
+const STATUS_DRDY_TEMP: u16 = 1 << 5;
+const STATUS_DRDY_GYR: u16 = 1 << 6;
+const STATUS_DRDY_ACC: u16 = 1 << 7;
+
+const FEATURE_IO1_ACTIVATED: u16 = 0x1;
+const FEATURE_IO1_ERROR_MASK: u16 = 0xF;
+const FEATURE_IO1_SC_ST_COMPLETE: u16 = 1 << 4;
+const FEATURE_IO1_ST_RESULT: u16 = 1 << 6;
+const FEATURE_IO1_AXIS_MAP_COMPLETE: u16 = 1 << 10;
+const FEATURE_IO1_NO_ERROR: u16 = 0x5;
+const FEATURE_IO1_AXIS_MAP_NOT_PROCESSED: u16 = 0x6;
+
+const DATA_STATUS_OUT_OF_BOUND: u16 = 1 << 0;
+const DATA_STATUS_TX_READY: u16 = 1 << 1;
+
+const CONF_MODE_SHIFT: u16 = 12;
+const CONF_MODE_MASK: u16 = 0x7;
+const GYR_MODE_SUSPEND: u16 = 0x1;
+
+/// Error returned by [`MockBmi323`] for injected failures and wrong addresses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MockError {
+  /// The transfer was not addressed to the BMI323 (0x68).
+  WrongAddress(u8),
+  /// Failure injected with [`MockBmi323::fail_transfers`].
+  Injected,
+  /// A read was issued without first writing a register address.
+  NoRegister,
+}
+
+impl embedded_hal_async::i2c::Error for MockError {
+  fn kind(&self) -> ErrorKind {
+    match self {
+      MockError::WrongAddress(_) => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address),
+      MockError::Injected => ErrorKind::Bus,
+      MockError::NoRegister => ErrorKind::Other,
+    }
+  }
+}
+
+/// In-memory BMI323.
+///
+/// Register access from tests goes through shared references, so the same
+/// mock can be handed to the driver as `&MockBmi323` and inspected meanwhile.
+pub struct MockBmi323 {
+  state: RefCell<State>,
+}
+
+struct State {
+  regs: [u16; REG_COUNT],
+  feature: [u16; FEATURE_WORDS],
+  ext_addr: usize,
+  fifo: [u16; FIFO_WORDS],
+  fifo_head: usize,
+  fifo_len: usize,
+  fail: u32,
+  transfers: u32,
+}
+
+impl MockBmi323 {
+  /// A freshly powered-up device.
+  pub fn new() -> Self {
+    let mut state = State {
+      regs: [0; REG_COUNT],
+      feature: [0; FEATURE_WORDS],
+      ext_addr: 0,
+      fifo: [0; FIFO_WORDS],
+      fifo_head: 0,
+      fifo_len: 0,
+      fail: 0,
+      transfers: 0,
+    };
+    state.reset();
+    Self { state: RefCell::new(state) }
+  }
+
+  /// Current value of a register (no read side effects).
+  pub fn register(&self, reg: Reg) -> u16 {
+    self.state.borrow().regs[reg as usize]
+  }
+
+  /// Overwrite a register (no write side effects).
+  pub fn set_register(&self, reg: Reg, value: u16) {
+    self.state.borrow_mut().regs[reg as usize] = value;
+  }
+
+  /// Word at extended address `addr` of the feature memory.
+  pub fn feature_word(&self, addr: u16) -> u16 {
+    self.state.borrow().feature[addr as usize]
+  }
+
+  pub fn set_feature_word(&self, addr: u16, value: u16) {
+    self.state.borrow_mut().feature[addr as usize] = value;
+  }
+
+  /// Whether `FEATURE_CTRL.engine_en` is set.
+  pub fn feature_engine_enabled(&self) -> bool {
+    self.register(Reg::FeatureCtrl) & 1 != 0
+  }
+
+  /// Raw accelerometer sample returned from `ACC_DATA_X/Y/Z`.
+  pub fn set_accel_raw(&self, xyz: [i16; 3]) {
+    let mut s = self.state.borrow_mut();
+    s.regs[Reg::AccDataX as usize] = xyz[0] as u16;
+    s.regs[ACC_DATA_Y as usize] = xyz[1] as u16;
+    s.regs[ACC_DATA_Z as usize] = xyz[2] as u16;
+  }
+
+  /// Raw gyroscope sample returned from `GYR_DATA_X/Y/Z`.
+  pub fn set_gyro_raw(&self, xyz: [i16; 3]) {
+    let mut s = self.state.borrow_mut();
+    s.regs[Reg::GyrDataX as usize] = xyz[0] as u16;
+    s.regs[GYR_DATA_Y as usize] = xyz[1] as u16;
+    s.regs[GYR_DATA_Z as usize] = xyz[2] as u16;
+  }
+
+  /// Raw value returned from `TEMP_DATA`.
+  pub fn set_temperature_raw(&self, raw: i16) {
+    self.set_register(Reg::TempData, raw as u16);
+  }
+
+  /// Append words to the FIFO; returns how many fit (the rest is dropped).
+  pub fn push_fifo(&self, words: &[u16]) -> usize {
+    let mut s = self.state.borrow_mut();
+    let n = words.len().min(FIFO_WORDS - s.fifo_len);
+    for &w in &words[..n] {
+      let tail = (s.fifo_head + s.fifo_len) % FIFO_WORDS;
+      s.fifo[tail] = w;
+      s.fifo_len += 1;
+    }
+    n
+  }
+
+  /// Words currently stored in the FIFO.
+  pub fn fifo_len(&self) -> usize {
+    self.state.borrow().fifo_len
+  }
+
+  /// Set bits in `INT_STATUS_INT1` (see [`IntStatus`](crate::interrupt::IntStatus) for the layout).
+  pub fn raise_int1(&self, bits: u16) {
+    self.state.borrow_mut().regs[Reg::IntStatusInt1 as usize] |= bits;
+  }
+
+  /// Set bits in `INT_STATUS_INT2`.
+  pub fn raise_int2(&self, bits: u16) {
+    self.state.borrow_mut().regs[Reg::IntStatusInt2 as usize] |= bits;
+  }
+
+  /// Value returned by the next read of `FEATURE_EVENT_EXT`.
+  pub fn set_feature_event_ext(&self, value: u16) {
+    self.set_register(Reg::FeatureEventExt, value);
+  }
+
+  /// Set bits in `INT_STATUS_IBI` (I3C in-band interrupt status).
+  pub fn raise_ibi(&self, bits: u16) {
+    self.state.borrow_mut().regs[INT_STATUS_IBI as usize] |= bits;
+  }
+
+  /// Register access by raw address, for registers without a [`Reg`] name.
+  pub(crate) fn raw(&self, addr: u8) -> u16 {
+    self.state.borrow().regs[addr as usize % REG_COUNT]
+  }
+
+  pub(crate) fn set_raw(&self, addr: u8, value: u16) {
+    self.state.borrow_mut().regs[addr as usize % REG_COUNT] = value;
+  }
+
+  /// Discard up to `n` of the oldest FIFO words.
+  pub(crate) fn drop_fifo(&self, n: usize) {
+    let mut s = self.state.borrow_mut();
+    let n = n.min(s.fifo_len);
+    s.fifo_head = (s.fifo_head + n) % FIFO_WORDS;
+    s.fifo_len -= n;
+  }
+
+  /// Make the next `n` transfers fail with [`MockError::Injected`].
+  pub fn fail_transfers(&self, n: u32) {
+    self.state.borrow_mut().fail = n;
+  }
+
+  /// Number of transfers addressed to the device so far (including failed ones).
+  pub fn transfers(&self) -> u32 {
+    self.state.borrow().transfers
+  }
+
+  fn transfer(&self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), MockError> {
+    if address != ADDR_I2C_PRIM {
+      return Err(MockError::WrongAddress(address));
+    }
+    let mut s = self.state.borrow_mut();
+    s.transfers = s.transfers.wrapping_add(1);
+    if s.fail > 0 {
+      s.fail -= 1;
+      return Err(MockError::Injected);
+    }
+
+    let mut reg = None;
+    for op in operations {
+      match op {
+        Operation::Write(bytes) => {
+          let data = match reg {
+            Some(_) => &bytes[..],
+            None => match bytes.split_first() {
+              Some((&r, rest)) => {
+                reg = Some(r);
+                rest
+              }
+              None => continue,
+            },
+          };
+          if let Some(r) = reg {
+            s.write(r, data);
+          }
+        }
+        Operation::Read(buf) => {
+          let r = reg.ok_or(MockError::NoRegister)?;
+          s.read(r, buf);
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+impl Default for MockBmi323 {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl State {
+  fn reset(&mut self) {
+    self.regs = [0; REG_COUNT];
+    self.feature = [0; FEATURE_WORDS];
+    self.ext_addr = 0;
+    self.fifo_head = 0;
+    self.fifo_len = 0;
+    self.regs[Reg::ChipId as usize] = BMI323_CHIP_ID as u16;
+    self.regs[Reg::Status as usize] = STATUS_POR;
+    self.regs[Reg::AccConf as usize] = 0x0028;
+    self.regs[Reg::GyrConf as usize] = 0x0048;
+  }
+
+  fn is_port(reg: u8) -> bool {
+    reg == Reg::FifoData as u8 || reg == Reg::FeatureDataTx as u8
+  }
+
+  fn read(&mut self, reg: u8, buf: &mut [u8]) {
+    let (dummy, data) = buf.split_at_mut(buf.len().min(2));
+    dummy.fill(0);
+    for (i, chunk) in data.chunks_mut(2).enumerate() {
+      let addr = if Self::is_port(reg) {
+        reg
+      } else {
+        reg.wrapping_add(i as u8)
+      };
+      let word = self.read_word(addr).to_le_bytes();
+      chunk.copy_from_slice(&word[..chunk.len()]);
+    }
+  }
+
+  fn read_word(&mut self, reg: u8) -> u16 {
+    let idx = reg as usize % REG_COUNT;
+    match reg {
+      r if r == Reg::Status as u8 => {
+        let v = self.regs[idx] | self.data_ready();
+        self.regs[idx] &= !STATUS_POR;
+        v
+      }
+      r if r == Reg::IntStatusInt1 as u8
+        || r == Reg::IntStatusInt2 as u8
+        || r == INT_STATUS_IBI
+        || r == Reg::FeatureEventExt as u8 =>
+      {
+        core::mem::take(&mut self.regs[idx])
+      }
+      r if r == Reg::FifoFillLevel as u8 => self.fifo_len as u16,
+      r if r == Reg::FifoData as u8 => self.pop_fifo(),
+      r if r == Reg::FeatureDataTx as u8 => match self.feature.get(self.ext_addr).copied() {
+        Some(v) => {
+          self.ext_addr += 1;
+          v
+        }
+        None => {
+          self.regs[Reg::FeatureDataStatus as usize] |= DATA_STATUS_OUT_OF_BOUND;
+          0
+        }
+      },
+      r if r == Reg::FeatureDataStatus as u8 => {
+        // The feature data port only works with the engine enabled.
+        let ready = if self.regs[Reg::FeatureCtrl as usize] & 1 != 0 {
+          DATA_STATUS_TX_READY
+        } else {
+          0
+        };
+        let v = self.regs[idx] | ready;
+        self.regs[idx] &= !DATA_STATUS_OUT_OF_BOUND;
+        v
+      }
+      _ => self.regs[idx],
+    }
+  }
+
+  fn write(&mut self, reg: u8, data: &[u8]) {
+    for (i, chunk) in data.chunks(2).enumerate() {
+      let addr = if Self::is_port(reg) {
+        reg
+      } else {
+        reg.wrapping_add(i as u8)
+      };
+      let word = match *chunk {
+        [lo, hi] => u16::from_le_bytes([lo, hi]),
+        [lo] => (self.regs[addr as usize % REG_COUNT] & 0xFF00) | lo as u16,
+        _ => unreachable!(),
+      };
+      self.write_word(addr, word);
+    }
+  }
+
+  fn write_word(&mut self, reg: u8, value: u16) {
+    let idx = reg as usize % REG_COUNT;
+    match reg {
+      r if r == Reg::Cmd as u8 => self.command(value),
+      r if r == Reg::FifoCtrl as u8 => {
+        if value & 1 != 0 {
+          self.fifo_head = 0;
+          self.fifo_len = 0;
+        }
+      }
+      r if r == Reg::FifoData as u8 => {}
+      r if r == Reg::FeatureCtrl as u8 => {
+        self.regs[idx] = value;
+        if value & 1 != 0 {
+          let io1 = &mut self.regs[Reg::FeatureIo1 as usize];
+          *io1 = (*io1 & !FEATURE_IO1_ERROR_MASK) | FEATURE_IO1_ACTIVATED;
+        }
+      }
+      r if r == Reg::FeatureDataAddr as u8 => {
+        self.regs[idx] = value;
+        self.ext_addr = value as usize;
+      }
+      r if r == Reg::FeatureDataTx as u8 => match self.feature.get_mut(self.ext_addr) {
+        Some(w) => {
+          *w = value;
+          self.ext_addr += 1;
+        }
+        None => self.regs[Reg::FeatureDataStatus as usize] |= DATA_STATUS_OUT_OF_BOUND,
+      },
+      _ => self.regs[idx] = value,
+    }
+  }
+
+  fn command(&mut self, cmd: u16) {
+    if cmd == Command::SoftReset as u16 {
+      self.reset();
+    } else if cmd == Command::AxisMapUpdate as u16 {
+      // The engine refuses the remap while a sensor is active.
+      let status = if self.data_ready() != 0 {
+        FEATURE_IO1_AXIS_MAP_NOT_PROCESSED
+      } else {
+        FEATURE_IO1_AXIS_MAP_COMPLETE | FEATURE_IO1_NO_ERROR
+      };
+      let io1 = &mut self.regs[Reg::FeatureIo1 as usize];
+      *io1 = (*io1 & !(FEATURE_IO1_ERROR_MASK | FEATURE_IO1_AXIS_MAP_COMPLETE)) | status;
+    } else if cmd == Command::SelfTestTrigger as u16 {
+      self.regs[Reg::FeatureIo1 as usize] |= FEATURE_IO1_SC_ST_COMPLETE | FEATURE_IO1_ST_RESULT;
+    }
+  }
+
+  fn data_ready(&self) -> u16 {
+    let acc = (self.regs[Reg::AccConf as usize] >> CONF_MODE_SHIFT) & CONF_MODE_MASK;
+    let gyr = (self.regs[Reg::GyrConf as usize] >> CONF_MODE_SHIFT) & CONF_MODE_MASK;
+    let mut v = 0;
+    if acc != 0 {
+      v |= STATUS_DRDY_ACC | STATUS_DRDY_TEMP;
+    }
+    if gyr > GYR_MODE_SUSPEND {
+      v |= STATUS_DRDY_GYR | STATUS_DRDY_TEMP;
+    }
+    v
+  }
+
+  fn pop_fifo(&mut self) -> u16 {
+    if self.fifo_len == 0 {
+      // Reading an empty FIFO returns the "invalid frame" marker.
+      return 0x8000;
+    }
+    let v = self.fifo[self.fifo_head];
+    self.fifo_head = (self.fifo_head + 1) % FIFO_WORDS;
+    self.fifo_len -= 1;
+    v
+  }
+}
+
+impl ErrorType for MockBmi323 {
+  type Error = MockError;
+}
+
+impl ErrorType for &MockBmi323 {
+  type Error = MockError;
+}
+
+impl I2c<SevenBitAddress> for MockBmi323 {
+  async fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+    MockBmi323::transfer(self, address, operations)
+  }
+}
+
+impl I2c<SevenBitAddress> for &MockBmi323 {
+  async fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+    MockBmi323::transfer(self, address, operations)
+  }
+}
+
+/// Delay that returns immediately; time does not matter to [`MockBmi323`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopDelay;
+
+impl DelayNs for NoopDelay {
+  async fn delay_ns(&mut self, _ns: u32) {}
+}
+
+/// Run a future to completion by polling it in a loop.
+///
+/// Good enough for futures that never wait on real I/O, such as the driver
+/// talking to [`MockBmi323`] with [`NoopDelay`].
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+  let mut fut = pin!(fut);
+  let mut cx = Context::from_waker(Waker::noop());
+  loop {
+    if let Poll::Ready(v) = fut.as_mut().poll(&mut cx) {
+      return v;
+    }
+  }
+}
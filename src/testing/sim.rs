@@ -0,0 +1,556 @@
+//! Behavioral BMI323 simulator driven by a scripted motion profile.
+//!
+//! [`Simulator`] wraps a [`MockBmi323`] and a clock. Time advances only when
+//! the driver waits on the [`SimDelay`] handed to it (or when test code calls
+//! [`Simulator::advance_ms`]). At each sample instant of the configured ODR the
+//! simulator:
+//!
+//! - derives accelerometer, gyroscope and temperature readings from the
+//!   current [`Motion`] and the integrated device attitude,
+//! - updates the data and sensor-time registers and appends a frame to the
+//!   FIFO (as selected by `FIFO_CONF`),
+//! - raises data-ready, FIFO watermark/full, any-/no-motion, step and tap
+//!   status bits on the pin selected in `INT_MAP1`/`INT_MAP2`.
+//!
+//! Feature events are only raised while the feature engine is active and the
+//! feature is enabled in `FEATURE_IO0`; any-/no-motion honour the configured
+//! duration. Everything else about the algorithms is deliberately simple.
+//!
+//! # Examples
+//!
+//! ```
+//! use bmi323::accel::{AccelConfig, AccelPowerMode};
+//! use bmi323::fifo::FifoConfig;
+//! use bmi323::testing::{block_on, sim::{Motion, Segment, Simulator}};
+//! # #[cfg(not(feature = "events"))]
+//! # {
+//!
+//! let script = [Segment::new(Motion::Rest, 500), Segment::new(Motion::Rotate { rate_dps: [0.0, 0.0, 90.0] }, 1000)];
+//! let sim = Simulator::new(&script);
+//! let mut imu: bmi323::Bmi323<_, _> = bmi323::Bmi323::new(&sim, sim.delay());
+//!
+//! block_on(async {
+//!   imu.init(false).await.unwrap();
+//!   imu.set_accel_conf(AccelConfig { mode: AccelPowerMode::Normal, ..AccelConfig::default() }).await.unwrap();
+//!   imu.set_fifo_config(FifoConfig { accel_en: true, ..FifoConfig::default() }).await.unwrap();
+//! });
+//! sim.advance_ms(100);
+//!
+//! let mut frames = [0u8; 120];
+//! let n = block_on(imu.read_fifo_bytes(&mut frames)).unwrap();
+//! assert!(n >= 6 * 9);
+//! # }
+//! ```
+
+use core::{cell::RefCell, f32::consts::PI};
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+use micromath::F32Ext;
+
+use super::{MockBmi323, MockError, FIFO_WORDS};
+use crate::{
+  accel::AccelConfig,
+  any_no_motion::AnyNoMotionConfig,
+  fifo::FifoConfig,
+  gyro::{GyroConfig, GyroPowerMode},
+  interrupt::{IntMap, IntPin},
+  FeatureAddr, Features, Reg,
+};
+
+const NS_PER_MS: u64 = 1_000_000;
+/// Sensor time LSB is 39.0625 µs = 625000/16 ns.
+const SENSOR_TIME_NS_NUM: u64 = 625_000;
+const SENSOR_TIME_NS_DEN: u64 = 16;
+const TEMP_LSB_PER_C: f32 = 512.0;
+const TEMP_OFFSET_C: f32 = 23.0;
+/// Feature-engine duration fields count in 20 ms ticks.
+const FEATURE_TICK_NS: u64 = 20 * NS_PER_MS;
+const TAP_PULSE_NS: u64 = 10 * NS_PER_MS;
+const TAP_SPACING_NS: u64 = 200 * NS_PER_MS;
+
+const INT_MAP2: u8 = 0x3B;
+const SENSOR_TIME_1: u8 = 0x0B;
+const STEP_COUNT_LSW: Reg = Reg::FeatureIo2;
+const STEP_COUNT_MSW: Reg = Reg::FeatureIo3;
+
+const ST_NO_MOTION: u16 = 1 << 0;
+const ST_ANY_MOTION: u16 = 1 << 1;
+const ST_STEP_DETECTOR: u16 = 1 << 4;
+const ST_STEP_COUNTER: u16 = 1 << 5;
+const ST_TAP: u16 = 1 << 8;
+const ST_TEMP_DRDY: u16 = 1 << 11;
+const ST_GYR_DRDY: u16 = 1 << 12;
+const ST_ACC_DRDY: u16 = 1 << 13;
+const ST_FIFO_WM: u16 = 1 << 14;
+const ST_FIFO_FULL: u16 = 1 << 15;
+
+const EXT_S_TAP: u16 = 1 << 3;
+const EXT_D_TAP: u16 = 1 << 4;
+const EXT_T_TAP: u16 = 1 << 5;
+
+/// What the device is doing during a [`Segment`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Motion {
+  /// Lying still.
+  Rest,
+  /// Constant angular rate in °/s about the device x/y/z axes.
+  Rotate { rate_dps: [f32; 3] },
+  /// `count` taps (1–3, 200 ms apart) of `peak_g` along device `axis`
+  /// (0 = x, 1 = y, 2 = z) at the start of the segment. The tap event is
+  /// reported when the segment ends.
+  Tap { axis: usize, peak_g: f32, count: u8 },
+  /// Walking: vertical bounce of `amplitude_g`, one step per period.
+  Walk { steps_per_s: f32, amplitude_g: f32 },
+}
+
+/// One entry of the motion script.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+  pub motion: Motion,
+  pub duration_ms: u32,
+}
+
+impl Segment {
+  pub const fn new(motion: Motion, duration_ms: u32) -> Self {
+    Self { motion, duration_ms }
+  }
+}
+
+/// Register model plus motion script and clock.
+///
+/// The script starts with the first sample, i.e. once a sensor is enabled, so
+/// the time spent in `init` and feature setup does not eat into it. The device
+/// rests once the script has run out.
+pub struct Simulator<'a> {
+  mock: MockBmi323,
+  script: &'a [Segment],
+  state: RefCell<SimState>,
+}
+
+struct SimState {
+  now_ns: u64,
+  next_sample_ns: u64,
+  /// Clock value of the first sample; script time is measured from here.
+  script_start_ns: Option<u64>,
+  /// Device-to-world rotation; columns are the device axes in world frame.
+  attitude: [[f32; 3]; 3],
+  segment: Option<usize>,
+  segment_steps: u32,
+  still_ns: u64,
+  moving_ns: u64,
+  no_motion_fired: bool,
+  any_motion_fired: bool,
+  steps: u32,
+  temperature_c: f32,
+  accel_noise_g: f32,
+  gyro_noise_dps: f32,
+  rng: u32,
+}
+
+/// Delay that advances the simulator clock instead of waiting.
+pub struct SimDelay<'s, 'a> {
+  sim: &'s Simulator<'a>,
+}
+
+impl<'a> Simulator<'a> {
+  pub fn new(script: &'a [Segment]) -> Self {
+    Self {
+      mock: MockBmi323::new(),
+      script,
+      state: RefCell::new(SimState {
+        now_ns: 0,
+        next_sample_ns: 0,
+        script_start_ns: None,
+        attitude: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]],
+        segment: None,
+        segment_steps: 0,
+        still_ns: 0,
+        moving_ns: 0,
+        no_motion_fired: false,
+        any_motion_fired: false,
+        steps: 0,
+        temperature_c: 25.0,
+        accel_noise_g: 0.0,
+        gyro_noise_dps: 0.0,
+        rng: 0x2545_f491,
+      }),
+    }
+  }
+
+  /// The underlying register model, for stimuli and inspection.
+  pub fn mock(&self) -> &MockBmi323 {
+    &self.mock
+  }
+
+  /// A delay provider bound to this simulator's clock.
+  pub fn delay(&self) -> SimDelay<'_, 'a> {
+    SimDelay { sim: self }
+  }
+
+  /// Simulated time since creation in nanoseconds.
+  pub fn now_ns(&self) -> u64 {
+    self.state.borrow().now_ns
+  }
+
+  /// Die temperature reported from now on.
+  pub fn set_temperature_c(&self, celsius: f32) {
+    self.state.borrow_mut().temperature_c = celsius;
+  }
+
+  /// Add uniform noise of the given amplitude to every sample (deterministic sequence).
+  pub fn set_noise(&self, accel_g: f32, gyro_dps: f32) {
+    let mut st = self.state.borrow_mut();
+    st.accel_noise_g = accel_g;
+    st.gyro_noise_dps = gyro_dps;
+  }
+
+  pub fn advance_ms(&self, ms: u32) {
+    self.advance_ns(ms as u64 * NS_PER_MS);
+  }
+
+  /// Advance the clock, producing every sample that falls due on the way.
+  pub fn advance_ns(&self, ns: u64) {
+    let mut st = self.state.borrow_mut();
+    let end = st.now_ns + ns;
+    loop {
+      let Some(period) = self.sample_period_ns() else {
+        st.next_sample_ns = end;
+        break;
+      };
+      let t = st.next_sample_ns.max(st.now_ns);
+      if t > end {
+        break;
+      }
+      let dt = t - st.now_ns;
+      st.now_ns = t;
+      self.sample(&mut st, t, if dt == 0 { period } else { dt });
+      st.next_sample_ns = t + period;
+    }
+    st.now_ns = end;
+  }
+
+  fn accel_conf(&self) -> Option<AccelConfig> {
+    AccelConfig::try_from(self.mock.register(Reg::AccConf).to_le_bytes()).ok()
+  }
+
+  fn gyro_conf(&self) -> Option<GyroConfig> {
+    GyroConfig::try_from(self.mock.register(Reg::GyrConf).to_le_bytes()).ok()
+  }
+
+  fn accel_on(cfg: &Option<AccelConfig>) -> bool {
+    cfg.is_some_and(|c| c.validate().is_ok() && c.mode != crate::accel::AccelPowerMode::Disable)
+  }
+
+  fn gyro_on(cfg: &Option<GyroConfig>) -> bool {
+    cfg.is_some_and(|c| !matches!(c.mode, GyroPowerMode::Disable | GyroPowerMode::Suspend))
+  }
+
+  /// Sample period of the faster enabled sensor, `None` if both are off.
+  fn sample_period_ns(&self) -> Option<u64> {
+    let acc = self
+      .accel_conf()
+      .filter(|_| Self::accel_on(&self.accel_conf()))
+      .map(|c| c.odr.hz());
+    let gyr = self
+      .gyro_conf()
+      .filter(|_| Self::gyro_on(&self.gyro_conf()))
+      .map(|c| c.odr.hz());
+    let hz = match (acc, gyr) {
+      (Some(a), Some(g)) => a.max(g),
+      (Some(v), None) | (None, Some(v)) => v,
+      (None, None) => return None,
+    };
+    Some((1e9 / hz) as u64)
+  }
+
+  /// Index and start time of the segment active at `t` (`script.len()` once finished).
+  fn segment_at(&self, t: u64) -> (usize, u64) {
+    let mut start = 0;
+    for (i, seg) in self.script.iter().enumerate() {
+      let end = start + seg.duration_ms as u64 * NS_PER_MS;
+      if t < end {
+        return (i, start);
+      }
+      start = end;
+    }
+    (self.script.len(), start)
+  }
+
+  fn motion(&self, idx: usize) -> Motion {
+    self.script.get(idx).map_or(Motion::Rest, |s| s.motion)
+  }
+
+  fn sample(&self, st: &mut SimState, t: u64, dt: u64) {
+    let script_t = t - *st.script_start_ns.get_or_insert(t);
+    let (idx, start) = self.segment_at(script_t);
+    if st.segment != Some(idx) {
+      if let Some(prev) = st.segment {
+        self.segment_finished(prev);
+      }
+      st.segment = Some(idx);
+      st.segment_steps = 0;
+    }
+
+    let motion = self.motion(idx);
+    let local = script_t - start;
+    let local_s = local as f32 / 1e9;
+    let mut rate_dps = [0.0; 3];
+    let mut lin_device = [0.0; 3];
+    let mut lin_world = [0.0; 3];
+    match motion {
+      Motion::Rest => {}
+      Motion::Rotate { rate_dps: r } => rate_dps = r,
+      Motion::Tap { axis, peak_g, count } => {
+        let pulse = local % TAP_SPACING_NS;
+        if local / TAP_SPACING_NS < count as u64 && pulse < TAP_PULSE_NS && axis < 3 {
+          lin_device[axis] = peak_g;
+        }
+      }
+      Motion::Walk { steps_per_s, amplitude_g } => {
+        lin_world[2] = amplitude_g * F32Ext::sin(2.0 * PI * steps_per_s * local_s);
+      }
+    }
+
+    rotate(&mut st.attitude, rate_dps, dt as f32 / 1e9);
+    let mut accel = [0.0; 3];
+    for (i, a) in accel.iter_mut().enumerate() {
+      // Specific force: +1 g up, plus linear acceleration (world then device frame).
+      *a = st.attitude[2][i] * (1.0 + lin_world[2])
+        + st.attitude[0][i] * lin_world[0]
+        + st.attitude[1][i] * lin_world[1]
+        + lin_device[i];
+    }
+    for (a, r) in accel.iter_mut().zip(rate_dps.iter_mut()) {
+      *a += st.accel_noise_g * st.noise();
+      *r += st.gyro_noise_dps * st.noise();
+    }
+
+    let map = self.int_map();
+    self.write_data(st, t, accel, rate_dps, &map);
+    self.push_fifo(&map);
+    if self.mock.feature_engine_enabled() {
+      self.motion_features(st, motion, dt, &map);
+      if let Motion::Walk { steps_per_s, .. } = motion {
+        let n = (local_s * steps_per_s) as u32;
+        if n > st.segment_steps {
+          self.steps(st, n - st.segment_steps, &map);
+          st.segment_steps = n;
+        }
+      }
+    }
+  }
+
+  fn write_data(&self, st: &SimState, t: u64, accel: [f32; 3], gyro: [f32; 3], map: &IntMap) {
+    let acc_cfg = self.accel_conf();
+    let gyr_cfg = self.gyro_conf();
+    if Self::accel_on(&acc_cfg) {
+      let scale = acc_cfg.map_or(1.0 / 4096.0, |c| c.range.multiplier());
+      self.mock.set_accel_raw(accel.map(|g| to_raw(g / scale)));
+      self.raise(map.accel_data_ready, ST_ACC_DRDY);
+    }
+    if Self::gyro_on(&gyr_cfg) {
+      let scale = gyr_cfg.map_or(2000.0 / 32767.0, |c| c.range.multiplier());
+      self.mock.set_gyro_raw(gyro.map(|dps| to_raw(dps / scale)));
+      self.raise(map.gyro_data_ready, ST_GYR_DRDY);
+    }
+    self
+      .mock
+      .set_temperature_raw(to_raw((st.temperature_c - TEMP_OFFSET_C) * TEMP_LSB_PER_C));
+    self.raise(map.temp_data_ready, ST_TEMP_DRDY);
+
+    let time = (t * SENSOR_TIME_NS_DEN / SENSOR_TIME_NS_NUM) as u32;
+    self.mock.set_register(Reg::SensorTime0, time as u16);
+    self.mock.set_raw(SENSOR_TIME_1, (time >> 16) as u16);
+  }
+
+  /// Append one frame (accel, gyro, temperature, sensor time — as enabled).
+  fn push_fifo(&self, map: &IntMap) {
+    let cfg = FifoConfig::from(self.mock.register(Reg::FifoConf).to_le_bytes());
+    let mut frame = [0u16; 8];
+    let mut len = 0;
+    let mut put = |reg: u8, words: u8| {
+      for i in 0..words {
+        frame[len] = self.mock.raw(reg + i);
+        len += 1;
+      }
+    };
+    if cfg.accel_en {
+      put(Reg::AccDataX as u8, 3);
+    }
+    if cfg.gyro_en {
+      put(Reg::GyrDataX as u8, 3);
+    }
+    if cfg.temp_en {
+      put(Reg::TempData as u8, 1);
+    }
+    if cfg.time_en {
+      put(Reg::SensorTime0 as u8, 1);
+    }
+    if len == 0 {
+      return;
+    }
+
+    let room = FIFO_WORDS - self.mock.fifo_len();
+    if room < len {
+      if cfg.stop_on_full {
+        self.raise(map.fifo_full, ST_FIFO_FULL);
+        return;
+      }
+      self.mock.drop_fifo(len - room);
+    }
+    self.mock.push_fifo(&frame[..len]);
+
+    let fill = self.mock.fifo_len();
+    let watermark = (self.mock.register(Reg::FifoWatermark) & 0x3FF) as usize;
+    if watermark > 0 && fill >= watermark {
+      self.raise(map.fifo_watermark, ST_FIFO_WM);
+    }
+    if FIFO_WORDS - fill < len {
+      self.raise(map.fifo_full, ST_FIFO_FULL);
+    }
+  }
+
+  fn motion_features(&self, st: &mut SimState, motion: Motion, dt: u64, map: &IntMap) {
+    let features = self.features();
+    if motion == Motion::Rest {
+      st.moving_ns = 0;
+      st.any_motion_fired = false;
+      st.still_ns += dt;
+    } else {
+      st.still_ns = 0;
+      st.no_motion_fired = false;
+      st.moving_ns += dt;
+    }
+
+    let any = features.any_motion_x || features.any_motion_y || features.any_motion_z;
+    if any && !st.any_motion_fired && st.moving_ns >= self.feature_duration_ns(FeatureAddr::AnyMotion) {
+      st.any_motion_fired = true;
+      self.raise(map.any_motion, ST_ANY_MOTION);
+    }
+    let no = features.no_motion_x || features.no_motion_y || features.no_motion_z;
+    if no && !st.no_motion_fired && st.still_ns >= self.feature_duration_ns(FeatureAddr::NoMotion) {
+      st.no_motion_fired = true;
+      self.raise(map.no_motion, ST_NO_MOTION);
+    }
+  }
+
+  fn steps(&self, st: &mut SimState, n: u32, map: &IntMap) {
+    let features = self.features();
+    if !(features.step_detector || features.step_counter) {
+      return;
+    }
+    let watermark = (self.mock.feature_word(FeatureAddr::StepCnt as u16) & 0x3FF) as u32;
+    for _ in 0..n {
+      st.steps += 1;
+      if features.step_detector {
+        self.raise(map.step_detector, ST_STEP_DETECTOR);
+      }
+      if features.step_counter && watermark > 0 && st.steps.is_multiple_of(watermark) {
+        self.raise(map.step_counter, ST_STEP_COUNTER);
+      }
+    }
+    self.mock.set_register(STEP_COUNT_LSW, st.steps as u16);
+    self.mock.set_register(STEP_COUNT_MSW, (st.steps >> 16) as u16);
+  }
+
+  fn segment_finished(&self, idx: usize) {
+    let Motion::Tap { count, .. } = self.motion(idx) else {
+      return;
+    };
+    if !self.mock.feature_engine_enabled() {
+      return;
+    }
+    let features = self.features();
+    let (enabled, bit) = match count {
+      0 => return,
+      1 => (features.tap_single, EXT_S_TAP),
+      2 => (features.tap_double, EXT_D_TAP),
+      _ => (features.tap_triple, EXT_T_TAP),
+    };
+    if enabled {
+      let ext = self.mock.register(Reg::FeatureEventExt) & !(EXT_S_TAP | EXT_D_TAP | EXT_T_TAP);
+      self.mock.set_feature_event_ext(ext | bit);
+      self.raise(self.int_map().tap, ST_TAP);
+    }
+  }
+
+  fn features(&self) -> Features {
+    Features::from(self.mock.register(Reg::FeatureIo0).to_le_bytes())
+  }
+
+  fn int_map(&self) -> IntMap {
+    let lo = self.mock.register(Reg::IntMap1).to_le_bytes();
+    let hi = self.mock.raw(INT_MAP2).to_le_bytes();
+    IntMap::try_from([lo[0], lo[1], hi[0], hi[1]]).unwrap_or_default()
+  }
+
+  fn feature_duration_ns(&self, addr: FeatureAddr) -> u64 {
+    let mut bytes = [0u8; 6];
+    for (i, w) in bytes.chunks_mut(2).enumerate() {
+      w.copy_from_slice(&self.mock.feature_word(addr as u16 + i as u16).to_le_bytes());
+    }
+    AnyNoMotionConfig::from(bytes).duration as u64 * FEATURE_TICK_NS
+  }
+
+  fn raise(&self, pin: IntPin, bit: u16) {
+    match pin {
+      IntPin::None => {}
+      IntPin::Int1 => self.mock.raise_int1(bit),
+      IntPin::Int2 => self.mock.raise_int2(bit),
+      IntPin::Ibi => self.mock.raise_ibi(bit),
+    }
+  }
+}
+
+impl SimState {
+  /// Uniform noise in [-1, 1) from a xorshift generator.
+  fn noise(&mut self) -> f32 {
+    self.rng ^= self.rng << 13;
+    self.rng ^= self.rng >> 17;
+    self.rng ^= self.rng << 5;
+    (self.rng as f32 / u32::MAX as f32) * 2.0 - 1.0
+  }
+}
+
+fn to_raw(v: f32) -> i16 {
+  F32Ext::round(v).clamp(i16::MIN as f32, i16::MAX as f32) as i16
+}
+
+/// Apply a body-frame rotation of `rate_dps` over `dt_s` seconds to `r`.
+fn rotate(r: &mut [[f32; 3]; 3], rate_dps: [f32; 3], dt_s: f32) {
+  let w = rate_dps.map(|d| d.to_radians() * dt_s);
+  let angle = F32Ext::sqrt(w[0] * w[0] + w[1] * w[1] + w[2] * w[2]);
+  if angle == 0.0 {
+    return;
+  }
+  let [x, y, z] = w.map(|v| v / angle);
+  let (s, c) = (F32Ext::sin(angle), F32Ext::cos(angle));
+  let t = 1.0 - c;
+  let d = [
+    [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+    [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+    [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+  ];
+  let m = *r;
+  for (i, row) in r.iter_mut().enumerate() {
+    for (j, v) in row.iter_mut().enumerate() {
+      *v = (0..3).map(|k| m[i][k] * d[k][j]).sum();
+    }
+  }
+}
+
+impl ErrorType for &Simulator<'_> {
+  type Error = MockError;
+}
+
+impl I2c<SevenBitAddress> for &Simulator<'_> {
+  async fn transaction(&mut self, address: u8, operations: &mut [Operation<'_>]) -> Result<(), Self::Error> {
+    self.mock.transfer(address, operations)
+  }
+}
+
+impl DelayNs for SimDelay<'_, '_> {
+  async fn delay_ns(&mut self, ns: u32) {
+    self.sim.advance_ns(ns as u64);
+  }
+}
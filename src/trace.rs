@@ -0,0 +1,420 @@
+//! I2C transaction recording and replay (`trace` feature).
+//!
+//! [`Recorder`] wraps any [`I2c`] implementation and hands every operation to a
+//! [`TraceSink`] together with a timestamp from a [`Clock`]. [`SliceSink`]
+//! stores the records in the compact binary layout below; a sink of your own
+//! can print them instead, since [`Record`] implements `Display`.
+//!
+//! [`Replay`] is an [`I2c`] implementation that serves a binary trace back to
+//! the driver: writes are compared against the recording and reads return the
+//! recorded bytes. Running the same driver calls on a host reproduces a field
+//! capture deterministically; the first divergence is reported as
+//! [`ReplayError::Mismatch`].
+//!
+//! # Format
+//!
+//! Each record is an 8-byte header followed by its payload:
+//!
+//! ```text
+//! offset  size  field
+//! 0       1     kind (0 = write, 1 = read, 2 = error)
+//! 1       1     7-bit device address
+//! 2       4     timestamp in µs (little-endian, wraps)
+//! 6       2     payload length (little-endian)
+//! 8       len   bytes written or read (empty for errors)
+//! ```
+//!
+//! A successful transaction produces one record per operation; a failed one
+//! produces a single error record.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(i2c: impl embedded_hal_async::i2c::I2c, delay: impl embedded_hal_async::delay::DelayNs + Clone, now_us: impl FnMut() -> u32) {
+//! use bmi323::{trace::{Recorder, Replay, SliceSink}, Bmi323};
+//!
+//! let mut buf = [0u8; 4096];
+//! let mut sink = SliceSink::new(&mut buf);
+//! {
+//!   # #[cfg(not(feature = "events"))]
+//!   let mut imu: Bmi323<_, _> = Bmi323::new(Recorder::new(i2c, &mut sink, now_us), delay.clone());
+//!   # #[cfg(feature = "events")]
+//!   # let mut imu = Bmi323::new(Recorder::new(i2c, &mut sink, now_us), delay.clone(), bmi323::Polling::from_millis(20));
+//!   imu.init(false).await.unwrap();
+//! }
+//!
+//! // Later, on a host, with the bytes of `sink.as_bytes()`:
+//! # #[cfg(not(feature = "events"))]
+//! let mut imu: Bmi323<_, _> = Bmi323::new(Replay::new(sink.as_bytes()), delay);
+//! # #[cfg(feature = "events")]
+//! # let mut imu = Bmi323::new(Replay::new(sink.as_bytes()), delay, bmi323::Polling::from_millis(20));
+//! imu.init(false).await.unwrap();
+//! # }
+//! ```
+
+use core::fmt;
+
+use embedded_hal_async::i2c::{self, ErrorKind, ErrorType, I2c, NoAcknowledgeSource, SevenBitAddress};
+
+/// Size of the binary record header.
+pub const HEADER_LEN: usize = 8;
+
+/// Largest payload the 16-bit length field can describe.
+pub const MAX_PAYLOAD: usize = u16::MAX as usize;
+
+/// Kind of a trace record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RecordKind {
+  Write = 0,
+  Read = 1,
+  Error = 2,
+}
+
+impl From<RecordKind> for u8 {
+  fn from(k: RecordKind) -> Self {
+    k as u8
+  }
+}
+
+impl TryFrom<u8> for RecordKind {
+  type Error = ();
+  fn try_from(v: u8) -> Result<Self, ()> {
+    match v {
+      0 => Ok(RecordKind::Write),
+      1 => Ok(RecordKind::Read),
+      2 => Ok(RecordKind::Error),
+      _ => Err(()),
+    }
+  }
+}
+
+/// One recorded I2C operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Record<'a> {
+  pub kind: RecordKind,
+  pub address: u8,
+  pub timestamp_us: u32,
+  pub data: &'a [u8],
+}
+
+impl<'a> Record<'a> {
+  /// Binary header for this record (see the [module docs](self)).
+  ///
+  /// Only valid for payloads up to [`MAX_PAYLOAD`] bytes; [`Recorder`] drops
+  /// longer ones rather than writing a wrong length.
+  pub fn header(&self) -> [u8; HEADER_LEN] {
+    let ts = self.timestamp_us.to_le_bytes();
+    let len = (self.data.len() as u16).to_le_bytes();
+    [
+      self.kind.into(),
+      self.address,
+      ts[0],
+      ts[1],
+      ts[2],
+      ts[3],
+      len[0],
+      len[1],
+    ]
+  }
+
+  /// Size of the encoded record in bytes.
+  pub fn encoded_len(&self) -> usize {
+    HEADER_LEN + self.data.len()
+  }
+
+  /// Decode the record at the start of `bytes`, returning it and the rest.
+  ///
+  /// Returns `None` if the header is truncated or invalid, or the payload is short.
+  pub fn decode(bytes: &'a [u8]) -> Option<(Self, &'a [u8])> {
+    let (h, rest) = bytes.split_at_checked(HEADER_LEN)?;
+    let kind = RecordKind::try_from(h[0]).ok()?;
+    let timestamp_us = u32::from_le_bytes([h[2], h[3], h[4], h[5]]);
+    let len = u16::from_le_bytes([h[6], h[7]]) as usize;
+    let (data, rest) = rest.split_at_checked(len)?;
+    Some((Record { kind, address: h[1], timestamp_us, data }, rest))
+  }
+}
+
+/// Text form: `<timestamp µs> <W|R|E> <address> <bytes…>`.
+impl fmt::Display for Record<'_> {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let kind = match self.kind {
+      RecordKind::Write => 'W',
+      RecordKind::Read => 'R',
+      RecordKind::Error => 'E',
+    };
+    write!(f, "{:>10} {} {:02x}", self.timestamp_us, kind, self.address)?;
+    for b in self.data {
+      write!(f, " {b:02x}")?;
+    }
+    Ok(())
+  }
+}
+
+/// Iterator over the records of a binary trace.
+///
+/// Stops at the end of the trace or at the first malformed record.
+#[derive(Debug, Clone)]
+pub struct Records<'a> {
+  rest: &'a [u8],
+}
+
+impl<'a> Records<'a> {
+  pub fn new(trace: &'a [u8]) -> Self {
+    Self { rest: trace }
+  }
+
+  /// Bytes not consumed yet (non-empty after the end if the trace is malformed).
+  pub fn remainder(&self) -> &'a [u8] {
+    self.rest
+  }
+}
+
+impl<'a> Iterator for Records<'a> {
+  type Item = Record<'a>;
+
+  fn next(&mut self) -> Option<Record<'a>> {
+    let (record, rest) = Record::decode(self.rest)?;
+    self.rest = rest;
+    Some(record)
+  }
+}
+
+/// Timestamp source for [`Recorder`].
+///
+/// Implemented for closures returning microseconds, e.g. `|| timer.now_us()`.
+pub trait Clock {
+  /// Current time in microseconds; wrapping is fine.
+  fn now_us(&mut self) -> u32;
+}
+
+impl<F: FnMut() -> u32> Clock for F {
+  fn now_us(&mut self) -> u32 {
+    self()
+  }
+}
+
+/// Destination for trace records.
+pub trait TraceSink {
+  /// Store one record. Returns `false` if it had to be dropped.
+  fn write_record(&mut self, record: &Record<'_>) -> bool;
+}
+
+impl<S: TraceSink + ?Sized> TraceSink for &mut S {
+  fn write_record(&mut self, record: &Record<'_>) -> bool {
+    (**self).write_record(record)
+  }
+}
+
+/// Sink that encodes records into a byte buffer; records that do not fit
+/// are dropped.
+#[derive(Debug)]
+pub struct SliceSink<'a> {
+  buf: &'a mut [u8],
+  len: usize,
+}
+
+impl<'a> SliceSink<'a> {
+  pub fn new(buf: &'a mut [u8]) -> Self {
+    Self { buf, len: 0 }
+  }
+
+  /// The encoded trace so far.
+  pub fn as_bytes(&self) -> &[u8] {
+    &self.buf[..self.len]
+  }
+
+  /// Discard all records.
+  pub fn clear(&mut self) {
+    self.len = 0;
+  }
+}
+
+impl TraceSink for SliceSink<'_> {
+  fn write_record(&mut self, record: &Record<'_>) -> bool {
+    let end = self.len + record.encoded_len();
+    if end > self.buf.len() {
+      return false;
+    }
+    let body = self.len + HEADER_LEN;
+    self.buf[self.len..body].copy_from_slice(&record.header());
+    self.buf[body..end].copy_from_slice(record.data);
+    self.len = end;
+    true
+  }
+}
+
+/// I2C wrapper that records every operation into a [`TraceSink`].
+pub struct Recorder<I, S, C> {
+  i2c: I,
+  sink: S,
+  clock: C,
+  dropped: u32,
+}
+
+impl<I, S, C> Recorder<I, S, C>
+where
+  S: TraceSink,
+  C: Clock,
+{
+  pub fn new(i2c: I, sink: S, clock: C) -> Self {
+    Self { i2c, sink, clock, dropped: 0 }
+  }
+
+  pub fn sink(&self) -> &S {
+    &self.sink
+  }
+
+  pub fn sink_mut(&mut self) -> &mut S {
+    &mut self.sink
+  }
+
+  /// Number of records the sink could not store or that exceeded [`MAX_PAYLOAD`].
+  pub fn dropped(&self) -> u32 {
+    self.dropped
+  }
+
+  /// Release the bus, sink and clock.
+  pub fn into_inner(self) -> (I, S, C) {
+    (self.i2c, self.sink, self.clock)
+  }
+
+  fn record(&mut self, kind: RecordKind, address: u8, timestamp_us: u32, data: &[u8]) {
+    // A truncated length field would misframe every following record.
+    if data.len() > MAX_PAYLOAD || !self.sink.write_record(&Record { kind, address, timestamp_us, data }) {
+      self.dropped = self.dropped.saturating_add(1);
+    }
+  }
+}
+
+impl<I: ErrorType, S, C> ErrorType for Recorder<I, S, C> {
+  type Error = I::Error;
+}
+
+impl<I, S, C> I2c<SevenBitAddress> for Recorder<I, S, C>
+where
+  I: I2c<SevenBitAddress>,
+  S: TraceSink,
+  C: Clock,
+{
+  async fn transaction(&mut self, address: u8, operations: &mut [i2c::Operation<'_>]) -> Result<(), Self::Error> {
+    let timestamp = self.clock.now_us();
+    let res = self.i2c.transaction(address, operations).await;
+    if res.is_err() {
+      self.record(RecordKind::Error, address, timestamp, &[]);
+      return res;
+    }
+    for op in operations.iter() {
+      match op {
+        i2c::Operation::Write(bytes) => self.record(RecordKind::Write, address, timestamp, bytes),
+        i2c::Operation::Read(bytes) => self.record(RecordKind::Read, address, timestamp, bytes),
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Error returned by [`Replay`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ReplayError {
+  /// The trace has no more records.
+  Exhausted,
+  /// The next record is not valid trace data.
+  Malformed,
+  /// The driver diverged from the recording at record `index`.
+  Mismatch { index: usize },
+  /// The recorded transaction failed; replayed as a bus error.
+  Recorded,
+}
+
+impl i2c::Error for ReplayError {
+  fn kind(&self) -> ErrorKind {
+    match self {
+      ReplayError::Recorded => ErrorKind::Bus,
+      ReplayError::Mismatch { .. } => ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data),
+      ReplayError::Exhausted | ReplayError::Malformed => ErrorKind::Other,
+    }
+  }
+}
+
+/// I2C implementation that plays back a binary trace.
+///
+/// Each operation consumes the next record. Writes must match the recorded
+/// address and bytes; reads must match address and length and are filled with
+/// the recorded data. Timestamps are ignored.
+#[derive(Debug, Clone)]
+pub struct Replay<'a> {
+  records: Records<'a>,
+  index: usize,
+}
+
+impl<'a> Replay<'a> {
+  pub fn new(trace: &'a [u8]) -> Self {
+    Self { records: Records::new(trace), index: 0 }
+  }
+
+  /// Number of records consumed so far.
+  pub fn position(&self) -> usize {
+    self.index
+  }
+
+  /// `true` once every record has been played back.
+  pub fn is_finished(&self) -> bool {
+    self.records.remainder().is_empty()
+  }
+
+  fn next(&mut self) -> Result<Record<'a>, ReplayError> {
+    if self.is_finished() {
+      return Err(ReplayError::Exhausted);
+    }
+    let record = self.records.next().ok_or(ReplayError::Malformed)?;
+    self.index += 1;
+    Ok(record)
+  }
+
+  fn peek(&self) -> Option<Record<'a>> {
+    self.records.clone().next()
+  }
+
+  fn mismatch(&self) -> ReplayError {
+    ReplayError::Mismatch { index: self.index - 1 }
+  }
+}
+
+impl ErrorType for Replay<'_> {
+  type Error = ReplayError;
+}
+
+impl I2c<SevenBitAddress> for Replay<'_> {
+  async fn transaction(&mut self, address: u8, operations: &mut [i2c::Operation<'_>]) -> Result<(), Self::Error> {
+    if let Some(r) = self.peek().filter(|r| r.kind == RecordKind::Error) {
+      self.next()?;
+      return Err(if r.address == address {
+        ReplayError::Recorded
+      } else {
+        self.mismatch()
+      });
+    }
+    for op in operations {
+      let r = self.next()?;
+      let matches = r.address == address
+        && match op {
+          i2c::Operation::Write(bytes) => r.kind == RecordKind::Write && r.data == *bytes,
+          i2c::Operation::Read(buf) => {
+            let ok = r.kind == RecordKind::Read && r.data.len() == buf.len();
+            if ok {
+              buf.copy_from_slice(r.data);
+            }
+            ok
+          }
+        };
+      if !matches {
+        return Err(self.mismatch());
+      }
+    }
+    Ok(())
+  }
+}
@@ -24,8 +24,9 @@ pub struct XYZ {
 /// Higher ODR values reduce latency but increase power consumption
 /// and data bandwidth requirements. Choose based on your application's
 /// sampling rate needs.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OutputDataRate {
   Hz0_78 = 0x01,
   Hz1_56 = 0x02,
@@ -43,6 +44,28 @@ pub enum OutputDataRate {
   Hz6400 = 0x0E,
 }
 
+impl OutputDataRate {
+  /// Nominal rate in Hz.
+  pub fn hz(self) -> f32 {
+    match self {
+      OutputDataRate::Hz0_78 => 0.78125,
+      OutputDataRate::Hz1_56 => 1.5625,
+      OutputDataRate::Hz3_12 => 3.125,
+      OutputDataRate::Hz6_25 => 6.25,
+      OutputDataRate::Hz12_5 => 12.5,
+      OutputDataRate::Hz25 => 25.0,
+      OutputDataRate::Hz50 => 50.0,
+      OutputDataRate::Hz100 => 100.0,
+      OutputDataRate::Hz200 => 200.0,
+      OutputDataRate::Hz400 => 400.0,
+      OutputDataRate::Hz800 => 800.0,
+      OutputDataRate::Hz1600 => 1600.0,
+      OutputDataRate::Hz3200 => 3200.0,
+      OutputDataRate::Hz6400 => 6400.0,
+    }
+  }
+}
+
 impl From<OutputDataRate> for u8 {
   fn from(odr: OutputDataRate) -> Self {
     odr as u8
@@ -78,6 +101,7 @@ impl TryFrom<u8> for OutputDataRate {
 /// - `OdrQuarter`: fc ≈ ODR/4 (narrower bandwidth)
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Bandwidth {
   OdrHalf = 0x00,
   OdrQuarter = 0x01,
@@ -103,8 +127,9 @@ impl TryFrom<u8> for Bandwidth {
 
 /// Number of samples to average. Reduces noise at the cost of latency and
 /// responsiveness. Applies to both data and some feature engines.
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AverageNum {
   /// No averaging
   No = 0x00,
@@ -122,6 +147,13 @@ pub enum AverageNum {
   Avg64 = 0x06,
 }
 
+impl AverageNum {
+  /// Number of samples averaged.
+  pub fn samples(self) -> u8 {
+    1 << self as u8
+  }
+}
+
 impl From<AverageNum> for u8 {
   fn from(value: AverageNum) -> Self {
     value as u8
@@ -145,7 +177,38 @@ impl TryFrom<u8> for AverageNum {
   }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Highest ODR × averaging product the low-power duty cycle can sustain.
+const LOW_POWER_MAX_SAMPLE_RATE_HZ: f32 = 400.0;
+/// Lowest ODR supported by normal and high-performance modes.
+const CONTINUOUS_MIN_ODR: OutputDataRate = OutputDataRate::Hz12_5;
+
+/// Shared ODR/averaging rules for `ACC_CONF` and `GYR_CONF`.
+///
+/// - Low-power (duty-cycled) mode: ODR ≤ 400 Hz, and the averaging window
+///   must fit the duty cycle (`ODR × samples ≤ 400 Hz`, e.g. 64 samples only
+///   up to 6.25 Hz).
+/// - Normal and high-performance modes: ODR ≥ 12.5 Hz; averaging is ignored.
+pub(crate) fn check_odr_mode(
+  sensor: Sensor,
+  low_power: bool,
+  odr: OutputDataRate,
+  avg: AverageNum,
+) -> Result<(), crate::ConfigError> {
+  use crate::ConfigError;
+  if low_power {
+    if odr.hz() > LOW_POWER_MAX_SAMPLE_RATE_HZ {
+      return Err(ConfigError::OdrTooHigh { sensor, max: OutputDataRate::Hz400 });
+    }
+    if odr.hz() * avg.samples() as f32 > LOW_POWER_MAX_SAMPLE_RATE_HZ {
+      return Err(ConfigError::AveragingTooHigh { sensor, odr, avg });
+    }
+  } else if odr.hz() < CONTINUOUS_MIN_ODR.hz() {
+    return Err(ConfigError::OdrTooLow { sensor, min: CONTINUOUS_MIN_ODR });
+  }
+  Ok(())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Sensor {
   Accel,
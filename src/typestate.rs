@@ -0,0 +1,476 @@
+//! Optional typestate layer over [`Bmi323`].
+//!
+//! [`Device`] wraps the untyped driver and tracks in the type system whether
+//! the chip has been initialized and whether the feature engine is active, so
+//! register and feature-memory calls made in the wrong state do not compile:
+//!
+//! - [`Uninit`]: freshly wrapped, nothing verified yet.
+//! - [`Ready`]: reset and chip ID verified; sensor, FIFO and interrupt APIs available.
+//! - [`FeatureEngine`]: additionally, the feature engine is active; feature
+//!   configuration (tap, motion, orientation, step counter, ...) is available.
+//!
+//! Nothing else is tracked. In particular, whether the accelerometer or
+//! gyroscope is enabled is not part of the state, so calls the chip refuses
+//! while a sensor is active (such as [`Bmi323::set_axis_remap`]) still fail at
+//! run time. Calls that would switch the feature engine behind the wrapper's
+//! back (power settings with features, restoring a dump taken in the other
+//! state, a [`DeviceConfig`] that disagrees with the requested state) return
+//! [`Error::InvalidMode`] before writing anything.
+//!
+//! The untyped [`Bmi323`] API is unchanged; [`Device::into_inner`] returns to it
+//! at any time.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! # async fn example(imu: bmi323::Bmi323<impl embedded_hal_async::i2c::I2c, impl embedded_hal_async::delay::DelayNs>) {
+//! use bmi323::typestate::Device;
+//! use bmi323::{accel::AccelConfig, tap::TapConfig};
+//!
+//! let mut dev = Device::new(imu).init().await.unwrap();
+//! dev.set_accel_conf(AccelConfig::default()).await.unwrap();
+//! // dev.set_tap_conf(..) does not compile here: the engine is not active yet.
+//!
+//! let mut dev = dev.enable_feature_engine().await.unwrap();
+//! dev.enable_tap(true, true, false, TapConfig::default()).await.unwrap();
+//! # }
+//! ```
+
+use core::marker::PhantomData;
+
+use embedded_hal_async::{delay::DelayNs, i2c::*};
+use micromath::vector::Vector3d;
+
+use crate::{
+  accel::AccelConfig, alt::AltAutoConfig, alt::AltEnable, alt::AltSensorConfig, alt::AltStatus,
+  any_no_motion::AnyNoMotionConfig, axis_remap::AxisRemap, axis_remap::Rotation, config::ConfigDiff,
+  config::DeviceConfig, dump::RegisterDump, fifo::FifoConfig, fifo::FifoFrames, flat::FlatConfig, gyro::GyroConfig,
+  health::ErrorRegister, health::HealthReport, i3c::TcSyncConfig, interrupt::*, io::*, offset::AccelOffsetGain,
+  offset::GyroOffsetGain, orientation::OrientationConfig, power::PowerProfile, power::PowerSettings,
+  recovery::ConfigSnapshot, sig_motion::SigMotionConfig, tap::TapConfig, tilt::TiltConfig, Bmi323, Error,
+  FeatureDataStatus, FeatureEngineStatus, FeatureIo1, FeatureSettings, Features, Reg, SaturationFlags,
+};
+
+/// Wrapped but not yet initialized.
+#[derive(Debug)]
+pub struct Uninit;
+
+/// Reset and verified; the feature engine is not active.
+#[derive(Debug)]
+pub struct Ready;
+
+/// Reset, verified and with the feature engine active.
+#[derive(Debug)]
+pub struct FeatureEngine;
+
+mod sealed {
+  pub trait Sealed {}
+  impl Sealed for super::Uninit {}
+  impl Sealed for super::Ready {}
+  impl Sealed for super::FeatureEngine {}
+}
+
+/// Driver state marker.
+pub trait State: sealed::Sealed {}
+impl State for Uninit {}
+impl State for Ready {}
+impl State for FeatureEngine {}
+
+/// States in which the chip has been reset and its ID verified.
+pub trait Initialized: State {
+  /// Whether the feature engine is active in this state.
+  const FEATURE_ENGINE: bool;
+}
+impl Initialized for Ready {
+  const FEATURE_ENGINE: bool = false;
+}
+impl Initialized for FeatureEngine {
+  const FEATURE_ENGINE: bool = true;
+}
+
+/// [`Bmi323`] with its state `S` tracked at compile time.
+pub struct Device<S: State, I, D: DelayNs, W = ()> {
+  imu: Bmi323<I, D, W>,
+  _state: PhantomData<S>,
+}
+
+impl<S: State, I, D: DelayNs, W> Device<S, I, D, W> {
+  fn wrap(imu: Bmi323<I, D, W>) -> Self {
+    Self { imu, _state: PhantomData }
+  }
+
+  /// Drop the state tracking and return the untyped driver.
+  pub fn into_inner(self) -> Bmi323<I, D, W> {
+    self.imu
+  }
+}
+
+impl<I, D: DelayNs, W> Device<Uninit, I, D, W> {
+  /// Wrap an untyped driver. Its state is unknown until [`init`](Self::init).
+  pub fn new(imu: Bmi323<I, D, W>) -> Self {
+    Self::wrap(imu)
+  }
+}
+
+impl<I, D: DelayNs, W> From<Bmi323<I, D, W>> for Device<Uninit, I, D, W> {
+  fn from(imu: Bmi323<I, D, W>) -> Self {
+    Self::new(imu)
+  }
+}
+
+macro_rules! forward {
+  ($(fn $name:ident(&mut self $(, $arg:ident: $ty:ty)*) -> $ret:ty;)*) => {
+    $(
+      #[doc = concat!("See [`Bmi323::", stringify!($name), "`].")]
+      pub async fn $name(&mut self $(, $arg: $ty)*) -> Result<$ret, Error<E>> {
+        self.imu.$name($($arg),*).await
+      }
+    )*
+  };
+}
+
+impl<I, D, W, E> Device<Uninit, I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Reset and verify the chip (see [`Bmi323::init`]).
+  pub async fn init(mut self) -> Result<Device<Ready, I, D, W>, Error<E>> {
+    self.imu.init(false).await?;
+    Ok(Device::wrap(self.imu))
+  }
+
+  /// Reset and verify the chip, then activate the feature engine.
+  pub async fn init_with_feature_engine(mut self) -> Result<Device<FeatureEngine, I, D, W>, Error<E>> {
+    self.imu.init(true).await?;
+    Ok(Device::wrap(self.imu))
+  }
+}
+
+impl<S, I, D, W, E> Device<S, I, D, W>
+where
+  S: State,
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Reset the chip and write `cfg` (see [`DeviceConfig::apply`]).
+  ///
+  /// Returns [`Error::InvalidMode`] without writing anything if `cfg` enables
+  /// the feature engine; use [`apply_config_with_feature_engine`](Self::apply_config_with_feature_engine).
+  pub async fn apply_config(mut self, cfg: &DeviceConfig) -> Result<Device<Ready, I, D, W>, Error<E>> {
+    if cfg.features.is_some() {
+      return Err(Error::InvalidMode);
+    }
+    cfg.apply(&mut self.imu).await?;
+    Ok(Device::wrap(self.imu))
+  }
+
+  /// Reset the chip and write `cfg`, including its feature settings.
+  ///
+  /// Returns [`Error::InvalidMode`] without writing anything if `cfg.features` is `None`.
+  pub async fn apply_config_with_feature_engine(
+    mut self,
+    cfg: &DeviceConfig,
+  ) -> Result<Device<FeatureEngine, I, D, W>, Error<E>> {
+    if cfg.features.is_none() {
+      return Err(Error::InvalidMode);
+    }
+    cfg.apply(&mut self.imu).await?;
+    Ok(Device::wrap(self.imu))
+  }
+}
+
+impl<I, D, W, E> Device<Ready, I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Activate the feature engine (see [`Bmi323::enable_feature_engine`]).
+  pub async fn enable_feature_engine(mut self) -> Result<Device<FeatureEngine, I, D, W>, Error<E>> {
+    self.imu.enable_feature_engine().await?;
+    Ok(Device::wrap(self.imu))
+  }
+}
+
+impl<I, D, W, E> Device<FeatureEngine, I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Deactivate the feature engine (see [`Bmi323::disable_feature_engine`]).
+  ///
+  /// The chip must be soft-reset before the engine can be activated again.
+  pub async fn disable_feature_engine(mut self) -> Result<Device<Ready, I, D, W>, Error<E>> {
+    self.imu.disable_feature_engine().await?;
+    Ok(Device::wrap(self.imu))
+  }
+}
+
+impl<S, I, D, W, E> Device<S, I, D, W>
+where
+  S: Initialized,
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  /// Soft-reset the chip. All configuration is lost, so the state returns to [`Uninit`].
+  pub async fn soft_reset(mut self) -> Result<Device<Uninit, I, D, W>, Error<E>> {
+    self.imu.soft_reset().await?;
+    Ok(Device::wrap(self.imu))
+  }
+
+  /// Soft-reset and replay the recorded configuration (see [`Bmi323::recover`]).
+  ///
+  /// The replay re-enables the feature engine only if it was enabled before,
+  /// so the state is preserved.
+  pub async fn recover(&mut self) -> Result<(), Error<E>> {
+    self.imu.recover().await
+  }
+
+  /// Wait for the bus, then soft-reset and replay (see [`Bmi323::recover_bus`]).
+  pub async fn recover_bus(&mut self, attempts: u8) -> Result<(), Error<E>> {
+    self.imu.recover_bus(attempts).await
+  }
+
+  /// Restore a register dump (see [`Bmi323::restore_registers`]).
+  ///
+  /// Returns [`Error::InvalidMode`] without writing anything if the dump's
+  /// feature engine state differs from `S`.
+  pub async fn restore_registers(&mut self, dump: &RegisterDump) -> Result<(), Error<E>> {
+    if dump.feature_engine_enabled() != S::FEATURE_ENGINE {
+      return Err(Error::InvalidMode);
+    }
+    self.imu.restore_registers(dump).await
+  }
+
+  /// Apply a power preset (see [`Bmi323::apply_power_profile`]).
+  ///
+  /// Same restrictions as [`apply_power_settings`](Self::apply_power_settings).
+  pub async fn apply_power_profile(&mut self, profile: PowerProfile) -> Result<(), Error<E>> {
+    self.apply_power_settings(profile.settings()).await
+  }
+
+  /// Validate and apply power settings (see [`Bmi323::apply_power_settings`]).
+  ///
+  /// Returns [`Error::InvalidMode`] without writing anything if the settings
+  /// enable features while the feature engine is not active.
+  pub async fn apply_power_settings(&mut self, settings: PowerSettings) -> Result<(), Error<E>> {
+    if settings.features.is_some() && !S::FEATURE_ENGINE {
+      return Err(Error::InvalidMode);
+    }
+    self.imu.apply_power_settings(settings).await
+  }
+
+  /// Compare the device against `cfg` (see [`DeviceConfig::read_back`]).
+  pub async fn read_back_config(&mut self, cfg: &DeviceConfig) -> Result<ConfigDiff, Error<E>> {
+    cfg.read_back(&mut self.imu).await
+  }
+
+  /// See [`Bmi323::snapshot`].
+  pub fn snapshot(&self) -> &ConfigSnapshot {
+    self.imu.snapshot()
+  }
+
+  /// See [`Bmi323::clear_snapshot`].
+  pub fn clear_snapshot(&mut self) {
+    self.imu.clear_snapshot()
+  }
+
+  /// See [`Bmi323::consecutive_i2c_errors`].
+  pub fn consecutive_i2c_errors(&self) -> u16 {
+    self.imu.consecutive_i2c_errors()
+  }
+
+  /// See [`Bmi323::set_frame_transform`].
+  pub fn set_frame_transform(&mut self, rot: Option<Rotation>) {
+    self.imu.set_frame_transform(rot)
+  }
+
+  /// See [`Bmi323::frame_transform`].
+  pub fn frame_transform(&self) -> Option<Rotation> {
+    self.imu.frame_transform()
+  }
+
+  /// See [`Bmi323::parse_fifo`].
+  pub fn parse_fifo<'a>(&self, cfg: FifoConfig, words: &'a [u16]) -> FifoFrames<'a> {
+    self.imu.parse_fifo(cfg, words)
+  }
+
+  forward! {
+    fn get_id(&mut self) -> u8;
+    fn get_error(&mut self) -> ErrorRegister;
+    fn health_check(&mut self) -> HealthReport;
+    fn get_temperature_raw(&mut self) -> i16;
+    fn get_sensor_time(&mut self) -> u32;
+    fn get_saturation_flags(&mut self) -> SaturationFlags;
+    fn dump_registers(&mut self) -> RegisterDump;
+    fn read_register(&mut self, reg: Reg) -> u16;
+    fn is_feature_engine_enabled(&mut self) -> bool;
+    fn get_feature_io1(&mut self) -> FeatureIo1;
+    fn get_feature_engine_status(&mut self) -> FeatureEngineStatus;
+    fn get_feature_data_status(&mut self) -> FeatureDataStatus;
+
+    fn get_accel_conf(&mut self) -> AccelConfig;
+    fn set_accel_conf(&mut self, cfg: AccelConfig) -> ();
+    fn get_raw_accel_data(&mut self) -> Vector3d<i16>;
+    fn get_accel_data(&mut self) -> Vector3d<f32>;
+    fn get_gyro_conf(&mut self) -> GyroConfig;
+    fn set_gyro_conf(&mut self, cfg: GyroConfig) -> ();
+    fn get_raw_gyro_data(&mut self) -> Vector3d<i16>;
+    fn get_gyro_data(&mut self) -> Vector3d<f32>;
+    fn get_raw_accel_gyro_data(&mut self) -> (Vector3d<i16>, Vector3d<i16>);
+    fn get_accel_gyro_data(&mut self) -> (Vector3d<f32>, Vector3d<f32>);
+    fn set_mounting(&mut self, rot: Rotation) -> ();
+
+    fn set_fifo_config(&mut self, cfg: FifoConfig) -> ();
+    fn get_fifo_config(&mut self) -> FifoConfig;
+    fn set_fifo_watermark(&mut self, level_words: u16) -> ();
+    fn get_fifo_watermark(&mut self) -> u16;
+    fn get_fifo_fill_level(&mut self) -> u16;
+    fn fifo_flush(&mut self) -> ();
+    fn read_fifo_bytes(&mut self, out: &mut [u8]) -> usize;
+    fn read_fifo_words(&mut self, out: &mut [u16]) -> usize;
+
+    fn set_int_map(&mut self, map: IntMap) -> ();
+    fn get_int_map(&mut self) -> IntMap;
+    fn set_int_latch(&mut self, latch: bool) -> ();
+    fn get_int_latch(&mut self) -> bool;
+    fn set_int_pins(&mut self, pin1: IntConfig, pin2: IntConfig) -> ();
+    fn set_int_config(&mut self, latch: bool, pin1: IntConfig, pin2: IntConfig) -> ();
+    fn get_int_config(&mut self) -> (IntConfig, IntConfig);
+    fn get_int1_status(&mut self) -> IntStatus;
+    fn get_int2_status(&mut self) -> IntStatus;
+
+    fn get_accel_offset_gain(&mut self) -> AccelOffsetGain;
+    fn set_accel_offset_gain(&mut self, v: AccelOffsetGain) -> ();
+    fn get_gyro_offset_gain(&mut self) -> GyroOffsetGain;
+    fn set_gyro_offset_gain(&mut self, v: GyroOffsetGain) -> ();
+
+    fn set_alt_enable(&mut self, en: AltEnable) -> ();
+    fn set_alt_accel_conf(&mut self, cfg: AccelConfig) -> ();
+    fn set_alt_gyro_conf(&mut self, cfg: GyroConfig) -> ();
+    fn get_alt_status(&mut self) -> AltStatus;
+
+    fn get_i2c_if(&mut self) -> IoI2cIf;
+    fn set_i2c_if(&mut self, v: IoI2cIf) -> ();
+    fn enable_i2c_watchdog(&mut self, timeout: I2cWatchdogTimeout) -> ();
+    fn disable_i2c_watchdog(&mut self) -> ();
+    fn get_spi_if(&mut self) -> IoSpiIf;
+    fn set_spi_if(&mut self, v: IoSpiIf) -> ();
+    fn get_io_pad_strength(&mut self) -> IoPadStrength;
+    fn set_io_pad_strength(&mut self, v: IoPadStrength) -> ();
+    fn get_io_odr_deviation(&mut self) -> IoOdrDeviation;
+    fn set_io_odr_deviation(&mut self, v: IoOdrDeviation) -> ();
+    fn get_io_pdn_ctrl(&mut self) -> IoPdnCtrl;
+    fn set_io_pdn_ctrl(&mut self, v: IoPdnCtrl) -> ();
+  }
+}
+
+impl<I, D, W, E> Device<FeatureEngine, I, D, W>
+where
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+{
+  forward! {
+    fn get_feature_event_ext(&mut self) -> FeatureEventExt;
+    fn get_enabled_features(&mut self) -> Features;
+    fn set_enabled_features(&mut self, v: Features) -> ();
+    fn enable_features(&mut self, mask: Features) -> ();
+    fn disable_features(&mut self, mask: Features) -> ();
+    fn apply_features(&mut self, cfg: FeatureSettings) -> ();
+
+    fn set_axis_remap(&mut self, map: AxisRemap) -> ();
+    fn get_axis_remap(&mut self) -> AxisRemap;
+    fn set_axis_remap_safe(&mut self, map: AxisRemap) -> ();
+    fn set_any_motion_conf(&mut self, cfg: AnyNoMotionConfig) -> ();
+    fn get_any_motion_conf(&mut self) -> AnyNoMotionConfig;
+    fn enable_any_motion(&mut self, x: bool, y: bool, z: bool, cfg: AnyNoMotionConfig) -> ();
+    fn disable_any_motion(&mut self) -> ();
+    fn set_no_motion_conf(&mut self, cfg: AnyNoMotionConfig) -> ();
+    fn get_no_motion_conf(&mut self) -> AnyNoMotionConfig;
+    fn enable_no_motion(&mut self, x: bool, y: bool, z: bool, cfg: AnyNoMotionConfig) -> ();
+    fn disable_no_motion(&mut self) -> ();
+    fn set_flat_conf(&mut self, cfg: FlatConfig) -> ();
+    fn get_flat_conf(&mut self) -> FlatConfig;
+    fn enable_flat(&mut self, cfg: FlatConfig) -> ();
+    fn disable_flat(&mut self) -> ();
+    fn set_orientation_conf(&mut self, cfg: OrientationConfig) -> ();
+    fn get_orientation_conf(&mut self) -> OrientationConfig;
+    fn enable_orientation(&mut self, cfg: OrientationConfig) -> ();
+    fn disable_orientation(&mut self) -> ();
+    fn set_sign_motion_conf(&mut self, cfg: SigMotionConfig) -> ();
+    fn get_sign_motion_conf(&mut self) -> SigMotionConfig;
+    fn enable_sig_motion(&mut self, cfg: SigMotionConfig) -> ();
+    fn disable_sig_motion(&mut self) -> ();
+    fn set_tap_conf(&mut self, cfg: TapConfig) -> ();
+    fn get_tap_conf(&mut self) -> TapConfig;
+    fn enable_tap(&mut self, single: bool, double: bool, triple: bool, cfg: TapConfig) -> ();
+    fn disable_tap(&mut self) -> ();
+    fn set_tilt_conf(&mut self, cfg: TiltConfig) -> ();
+    fn get_tilt_conf(&mut self) -> TiltConfig;
+    fn enable_tilt(&mut self, cfg: TiltConfig) -> ();
+    fn disable_tilt(&mut self) -> ();
+    fn get_step_count(&mut self) -> u32;
+    fn set_step_watermark(&mut self, wm: u16) -> ();
+    fn get_step_watermark(&mut self) -> u16;
+    fn reset_step_counter(&mut self) -> ();
+    fn run_self_test(&mut self) -> u16;
+    fn set_self_test_select(&mut self, bytes: &[u8]) -> ();
+    fn get_self_test_select(&mut self, out: &mut [u8]) -> ();
+
+    fn set_gyro_sc_select(&mut self, bytes: &[u8]) -> ();
+    fn get_gyro_sc_select(&mut self, out: &mut [u8]) -> ();
+    fn set_gyro_sc_st_conf(&mut self, bytes: &[u8]) -> ();
+    fn get_gyro_sc_st_conf(&mut self, out: &mut [u8]) -> ();
+    fn get_gyro_sc_coefficients(&mut self, out: &mut [u8]) -> ();
+    fn set_alt_auto_config(&mut self, bytes: &[u8]) -> ();
+    fn get_alt_auto_config(&mut self, out: &mut [u8]) -> ();
+
+    fn set_alt_auto_conf(&mut self, cfg: AltAutoConfig) -> ();
+    fn get_alt_auto_conf(&mut self) -> AltAutoConfig;
+    fn configure_auto_power_switch(
+      &mut self,
+      user: AltSensorConfig,
+      alt: AltSensorConfig,
+      triggers: AltAutoConfig
+    ) -> ();
+    fn set_i3c_tc_sync_conf(&mut self, cfg: TcSyncConfig) -> ();
+    fn get_i3c_tc_sync_conf(&mut self) -> TcSyncConfig;
+    fn enable_i3c_tc_sync(&mut self, cfg: TcSyncConfig) -> ();
+    fn disable_i3c_tc_sync(&mut self) -> ();
+
+    fn read_feature_words(&mut self, addr: u16, out: &mut [u16]) -> ();
+    fn write_feature_words(&mut self, addr: u16, words: &[u16]) -> ();
+    fn read_feature_word(&mut self, addr: u16) -> u16;
+    fn write_feature_word(&mut self, addr: u16, value: u16) -> ();
+    fn update_feature_word(&mut self, addr: u16, f: impl FnOnce(u16) -> u16) -> u16;
+    fn get_feature_field(&mut self, addr: u16, mask: u16) -> u16;
+    fn set_feature_field(&mut self, addr: u16, mask: u16, value: u16) -> ();
+  }
+}
+
+#[cfg(feature = "events")]
+impl<S, I, D, W, E> Device<S, I, D, W>
+where
+  S: Initialized,
+  I: I2c<SevenBitAddress, Error = E>,
+  D: DelayNs,
+  W: crate::EventSource,
+{
+  /// See [`Bmi323::wait_event`].
+  pub async fn wait_event(&mut self) -> Result<crate::Event, Error<E, W::Error>> {
+    self.imu.wait_event().await
+  }
+
+  /// See [`Bmi323::wait_event_matching`].
+  pub async fn wait_event_matching(&mut self, filter: crate::EventFilter) -> Result<crate::Event, Error<E, W::Error>> {
+    self.imu.wait_event_matching(filter).await
+  }
+
+  /// See [`Bmi323::dispatch`].
+  pub async fn dispatch<H: crate::EventHandler>(
+    &mut self,
+    handler: &mut H,
+  ) -> Result<crate::Event, Error<E, W::Error>> {
+    self.imu.dispatch(handler).await
+  }
+}
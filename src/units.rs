@@ -0,0 +1,96 @@
+//! Serde adapters for raw feature fields with a physical unit.
+//!
+//! Human-readable formats (JSON, TOML, ...) carry the value in mg or ms, so
+//! a tuning file reads `"slope_threshold": 19.53125` instead of the raw `10`.
+//! Binary formats keep the raw field value. Each adapter takes the packbits
+//! field width (`Mg::<12>`), and deserialized values are rounded to the
+//! nearest step and clamped to that width, since packbits would otherwise
+//! drop the high bits on write.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) trait Raw: Copy + Serialize + for<'de> Deserialize<'de> {
+  fn to_f32(self) -> f32;
+  fn from_f32(v: f32) -> Self;
+}
+
+impl Raw for u8 {
+  fn to_f32(self) -> f32 {
+    self as f32
+  }
+  fn from_f32(v: f32) -> Self {
+    // `as` saturates, which also maps negative values and NaN to 0.
+    (v + 0.5) as u8
+  }
+}
+
+impl Raw for u16 {
+  fn to_f32(self) -> f32 {
+    self as f32
+  }
+  fn from_f32(v: f32) -> Self {
+    (v + 0.5) as u16
+  }
+}
+
+/// Round `v` (in LSB) and clamp it to an unsigned field of `bits` bits.
+fn clamp<T: Raw>(v: f32, bits: u32) -> T {
+  let max = ((1u32 << bits) - 1) as f32;
+  T::from_f32(v.min(max))
+}
+
+fn serialize<S: Serializer, T: Raw>(v: &T, lsb: f32, s: S) -> Result<S::Ok, S::Error> {
+  if s.is_human_readable() {
+    s.serialize_f32(v.to_f32() * lsb)
+  } else {
+    v.serialize(s)
+  }
+}
+
+fn deserialize<'de, D: Deserializer<'de>, T: Raw>(d: D, lsb: f32, bits: u32) -> Result<T, D::Error> {
+  if d.is_human_readable() {
+    Ok(clamp(f32::deserialize(d)? / lsb, bits))
+  } else {
+    Ok(clamp(T::deserialize(d)?.to_f32(), bits))
+  }
+}
+
+macro_rules! unit {
+  ($(#[$doc:meta])* $name:ident, $lsb:expr) => {
+    $(#[$doc])*
+    ///
+    /// `BITS` is the width of the packbits field.
+    pub(crate) struct $name<const BITS: u32>;
+
+    impl<const BITS: u32> $name<BITS> {
+      pub(crate) fn serialize<S: Serializer, T: Raw>(v: &T, s: S) -> Result<S::Ok, S::Error> {
+        serialize(v, $lsb, s)
+      }
+
+      pub(crate) fn deserialize<'de, D: Deserializer<'de>, T: Raw>(d: D) -> Result<T, D::Error> {
+        deserialize(d, $lsb, BITS)
+      }
+    }
+  };
+}
+
+unit!(
+  /// Acceleration in mg (LSB = 1/512 g).
+  Mg,
+  1000.0 / 512.0
+);
+unit!(
+  /// Time in ms (LSB = 5 ms).
+  Ms5,
+  5.0
+);
+unit!(
+  /// Time in ms (LSB = 20 ms).
+  Ms20,
+  20.0
+);
+unit!(
+  /// Time in ms (LSB = 40 ms).
+  Ms40,
+  40.0
+);
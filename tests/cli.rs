@@ -0,0 +1,74 @@
+#![cfg(feature = "linux")]
+
+use std::process::{Command, Output};
+
+fn cli(args: &[&str]) -> Output {
+  Command::new(env!("CARGO_BIN_EXE_bmi323-cli"))
+    .arg("--mock")
+    .args(args)
+    .output()
+    .unwrap()
+}
+
+fn stdout(out: &Output) -> String {
+  String::from_utf8_lossy(&out.stdout).into_owned()
+}
+
+#[test]
+fn regs_decodes_the_register_map() {
+  let out = cli(&["regs"]);
+  assert!(out.status.success());
+  let text = stdout(&out);
+  assert!(text.lines().next().unwrap().starts_with("0x00 CHIP_ID"));
+  assert!(text.contains("0x0043"));
+  assert!(text.contains("ErrorRegister { fatal: false"));
+}
+
+#[test]
+fn accel_applies_odr_mode_and_range() {
+  let out = cli(&["accel", "100", "normal", "8"]);
+  assert!(out.status.success());
+  let text = stdout(&out);
+  assert!(text.contains("odr: Hz100"));
+  assert!(text.contains("range: G8"));
+  assert!(text.contains("mode: Normal"));
+}
+
+#[test]
+fn stream_prints_the_requested_sample_count() {
+  let out = cli(&["stream", "csv", "2"]);
+  assert!(out.status.success());
+  let text = stdout(&out);
+  let lines: Vec<&str> = text.lines().collect();
+  assert_eq!(lines.len(), 3);
+  assert_eq!(lines[0], "t_ms,ax,ay,az,gx,gy,gz");
+  assert!(lines[1..].iter().all(|l| l.split(',').count() == 7));
+}
+
+#[test]
+fn bad_arguments_fail_with_a_message() {
+  let out = cli(&["accel", "fast", "normal"]);
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("invalid ODR `fast`"));
+
+  let out = cli(&["bogus"]);
+  assert!(!out.status.success());
+  assert!(String::from_utf8_lossy(&out.stderr).contains("usage:"));
+}
+
+#[test]
+fn selftest_activates_the_feature_engine() {
+  let out = cli(&["selftest"]);
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  assert!(stdout(&out).starts_with("self-test result: "));
+}
+
+#[test]
+fn features_dumps_feature_memory() {
+  let out = cli(&["features", "8"]);
+  assert!(out.status.success(), "{}", String::from_utf8_lossy(&out.stderr));
+  let text = stdout(&out);
+  assert_eq!(text.lines().count(), 1);
+  assert!(text.starts_with("0x0000:"));
+  assert_eq!(text.split_whitespace().count(), 9);
+}
@@ -0,0 +1,24 @@
+//! Driver fixture shared by the integration tests.
+
+#![allow(dead_code)]
+
+use bmi323::{testing::NoopDelay, Bmi323};
+use embedded_hal_async::{delay::DelayNs, i2c::I2c};
+
+/// The driver type for the enabled feature set; with `events` it polls the
+/// interrupt status instead of waiting on a pin.
+#[cfg(not(feature = "events"))]
+pub type Imu<I, D = NoopDelay> = Bmi323<I, D>;
+#[cfg(feature = "events")]
+pub type Imu<I, D = NoopDelay> = Bmi323<I, D, bmi323::Polling>;
+
+pub fn imu<I: I2c>(i2c: I) -> Imu<I> {
+  imu_with_delay(i2c, NoopDelay)
+}
+
+pub fn imu_with_delay<I: I2c, D: DelayNs>(i2c: I, delay: D) -> Imu<I, D> {
+  #[cfg(not(feature = "events"))]
+  return Bmi323::new(i2c, delay);
+  #[cfg(feature = "events")]
+  return Bmi323::new(i2c, delay, bmi323::Polling::from_millis(1));
+}
@@ -0,0 +1,84 @@
+use bmi323::{
+  accel::{AccelConfig, AccelRange},
+  any_no_motion::AnyNoMotionConfig,
+  axis_remap::{BoardDirection, Rotation},
+  config::{DeviceConfig, ProfileError, PROFILE_LEN},
+  interrupt::IntPin,
+  tap::TapConfig,
+  FeatureSettings, Features, OutputDataRate,
+};
+
+fn config() -> DeviceConfig {
+  let mut cfg = DeviceConfig {
+    accel: AccelConfig { odr: OutputDataRate::Hz100, range: AccelRange::G8, ..AccelConfig::default() },
+    fifo_watermark: 200,
+    int_latch: true,
+    features: Some(FeatureSettings {
+      any_motion: Some(AnyNoMotionConfig::default()),
+      tap: Some(TapConfig::default()),
+      step_watermark: Some(5),
+      enabled: Features::ANY_MOTION | Features::TAP,
+      ..Default::default()
+    }),
+    ..Default::default()
+  };
+  cfg.int2.enable = true;
+  cfg.int_map.any_motion = IntPin::Int2;
+  cfg
+}
+
+#[test]
+fn profile_round_trips() {
+  let cfg = config();
+  let bytes = cfg.to_bytes();
+  assert_eq!(DeviceConfig::from_bytes(&bytes), Ok(cfg));
+
+  let erased = [0xFF; PROFILE_LEN];
+  assert_eq!(DeviceConfig::from_bytes(&erased), Err(ProfileError::UnsupportedVersion(0xFF)));
+
+  let mut bad = bytes;
+  bad[2] = 0x0F; // reserved ODR code in ACC_CONF
+  assert_eq!(DeviceConfig::from_bytes(&bad), Err(ProfileError::InvalidField(2)));
+}
+
+#[test]
+fn serde_uses_units_in_human_readable_formats() {
+  let cfg = config();
+  let json = serde_json::to_value(cfg).unwrap();
+  let any = &json["features"]["any_motion"];
+  assert_eq!(any["slope_threshold"], 19.53125); // 10 LSB
+  assert_eq!(any["duration"], 200.0); // 10 × 20 ms
+  assert_eq!(json["accel"]["odr"], "Hz100");
+
+  let back: DeviceConfig = serde_json::from_value(json).unwrap();
+  assert_eq!(back, cfg);
+
+  let cfg: AnyNoMotionConfig = serde_json::from_str(
+    r#"{"slope_threshold": 100, "acc_ref_update": true, "hysteresis": 4, "duration": 1000, "wait_time": 60}"#,
+  )
+  .unwrap();
+  assert_eq!((cfg.slope_threshold, cfg.duration, cfg.wait_time), (51, 50, 3));
+}
+
+#[test]
+fn serde_clamps_units_to_the_field_width() {
+  // 1000 ms is 50 LSB, beyond the 3-bit wait time; 9000 mg overflows the 12-bit threshold.
+  let cfg: AnyNoMotionConfig = serde_json::from_str(
+    r#"{"slope_threshold": 9000, "acc_ref_update": false, "hysteresis": 4, "duration": 20, "wait_time": 1000}"#,
+  )
+  .unwrap();
+  assert_eq!((cfg.slope_threshold, cfg.wait_time), (0xFFF, 7));
+  let raw: [u8; 6] = cfg.into();
+  assert_eq!(AnyNoMotionConfig::from(raw), cfg);
+}
+
+#[test]
+fn serde_validates_rotations() {
+  let rot = Rotation::from_directions(BoardDirection::Forward, BoardDirection::Up).unwrap();
+  let json = serde_json::to_string(&rot).unwrap();
+  assert_eq!(serde_json::from_str::<Rotation>(&json).unwrap(), rot);
+
+  let mirror = "[[-1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]";
+  assert!(serde_json::from_str::<Rotation>(mirror).is_err());
+  assert!(serde_json::from_str::<Rotation>("[[2.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]").is_err());
+}
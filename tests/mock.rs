@@ -0,0 +1,301 @@
+use bmi323::{
+  accel::{AccelConfig, AccelPowerMode},
+  axis_remap::{AxisOrder, AxisRemap},
+  config::{ConfigDiff, DeviceConfig},
+  i3c::TcSyncConfig,
+  interrupt::{IntMap, IntPin},
+  io::{IoPadStrength, IoSpiIf},
+  offset::AccelOffsetGain,
+  power::PowerProfile,
+  tap::TapConfig,
+  testing::{block_on, MockBmi323},
+  trace::{RecordKind, Recorder, Records, SliceSink},
+  typestate::Device,
+  Error, FeatureSettings, Features, OutputDataRate, Reg,
+};
+
+mod common;
+use common::imu;
+
+#[test]
+fn init_verifies_chip_and_enables_engine() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+
+  block_on(imu.init(true)).unwrap();
+  assert!(mock.feature_engine_enabled());
+  assert_eq!(block_on(imu.get_id()).unwrap(), 0x43);
+}
+
+#[test]
+fn sensor_config_and_data() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(false)).unwrap();
+
+  let cfg = AccelConfig { odr: OutputDataRate::Hz200, mode: AccelPowerMode::Normal, ..AccelConfig::default() };
+  block_on(imu.set_accel_conf(cfg)).unwrap();
+  assert_eq!(block_on(imu.get_accel_conf()).unwrap(), cfg);
+
+  mock.set_accel_raw([1, -2, 3]);
+  let v = block_on(imu.get_raw_accel_data()).unwrap();
+  assert_eq!((v.x, v.y, v.z), (1, -2, 3));
+}
+
+#[test]
+fn invalid_config_is_not_written() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(false)).unwrap();
+
+  let cfg = AccelConfig { odr: OutputDataRate::Hz1600, mode: AccelPowerMode::LowPower, ..AccelConfig::default() };
+  assert!(matches!(block_on(imu.set_accel_conf(cfg)), Err(Error::Config(_))));
+  assert_eq!(mock.register(Reg::AccConf), 0x0028);
+}
+
+#[test]
+fn feature_config_round_trips_through_extended_memory() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(true)).unwrap();
+
+  let cfg = TapConfig::default();
+  block_on(imu.set_tap_conf(cfg)).unwrap();
+  assert_eq!(block_on(imu.get_tap_conf()).unwrap(), cfg);
+  assert_ne!(mock.feature_word(0x1E), 0);
+}
+
+#[test]
+fn feature_memory_words_and_fields() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(true)).unwrap();
+
+  let words: Vec<u16> = (0..40).map(|i| 0x1000 + i).collect();
+  block_on(imu.write_feature_words(0x100, &words)).unwrap();
+  let mut back = [0u16; 40];
+  block_on(imu.read_feature_words(0x100, &mut back)).unwrap();
+  assert_eq!(&back[..], &words[..]);
+  assert_eq!(mock.feature_word(0x127), 0x1027);
+
+  block_on(imu.write_feature_word(0x10, 0xFC00)).unwrap();
+  block_on(imu.set_feature_field(0x10, 0x03FF, 300)).unwrap();
+  assert_eq!(block_on(imu.read_feature_word(0x10)).unwrap(), 0xFC00 | 300);
+  assert_eq!(block_on(imu.get_feature_field(0x10, 0x03FF)).unwrap(), 300);
+  assert_eq!(block_on(imu.get_feature_field(0x10, 0)).unwrap(), 0);
+
+  let end = bmi323::FEATURE_MEMORY_WORDS - 2;
+  assert!(matches!(block_on(imu.read_feature_words(end, &mut back)), Err(Error::FeatureOutOfBounds)));
+}
+
+#[test]
+fn feature_mask_updates_and_batched_apply() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(true)).unwrap();
+
+  block_on(imu.enable_features(Features::TAP | Features::FLAT | Features::TILT)).unwrap();
+  block_on(imu.disable_tap()).unwrap();
+  let f = block_on(imu.get_enabled_features()).unwrap();
+  assert_eq!(f, Features::FLAT | Features::TILT);
+  assert!(f.contains(Features::TILT) && !f.tap_single);
+
+  let tap = TapConfig::default();
+  let cfg = FeatureSettings { tap: Some(tap), enabled: Features::TAP, ..Default::default() };
+  block_on(imu.apply_features(cfg)).unwrap();
+  assert_eq!(block_on(imu.get_tap_conf()).unwrap(), tap);
+  assert_eq!(mock.register(Reg::FeatureIo0), Features::TAP.bits());
+}
+
+#[test]
+fn device_config_applies_and_reads_back() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+
+  let mut cfg = DeviceConfig::default();
+  cfg.accel.mode = AccelPowerMode::Normal;
+  cfg.fifo_watermark = 64;
+  cfg.int1.enable = true;
+  cfg.int_map.tap = IntPin::Int1;
+  // Fails unless the remap is written before the sensors are enabled.
+  let remap = AxisRemap { order: AxisOrder::YXZ, ..Default::default() };
+  cfg.features = Some(FeatureSettings {
+    axis_remap: Some(remap),
+    tap: Some(TapConfig::default()),
+    enabled: Features::TAP,
+    ..Default::default()
+  });
+  block_on(cfg.apply(&mut imu)).unwrap();
+  assert!(mock.feature_engine_enabled());
+  assert!(block_on(cfg.read_back(&mut imu)).unwrap().is_empty());
+
+  mock.set_register(Reg::FifoWatermark, 10);
+  let diff = block_on(cfg.read_back(&mut imu)).unwrap();
+  assert_eq!(diff, ConfigDiff { fifo_watermark: true, ..Default::default() });
+
+  // The register holds 10 bits; a larger request is clamped, not a mismatch.
+  cfg.fifo_watermark = 5000;
+  block_on(imu.set_fifo_watermark(cfg.fifo_watermark)).unwrap();
+  assert!(block_on(cfg.read_back(&mut imu)).unwrap().is_empty());
+}
+
+#[test]
+fn i3c_tc_sync_and_ibi_routing() {
+  let mock = MockBmi323::new();
+  let mut buf = [0u8; 8192];
+  let mut sink = SliceSink::new(&mut buf);
+  {
+    let mut imu = imu(Recorder::new(&mock, &mut sink, || 0));
+    block_on(imu.init(true)).unwrap();
+
+    let cfg = TcSyncConfig::new(1000, 10, 1);
+    block_on(imu.enable_i3c_tc_sync(cfg)).unwrap();
+    let regs = [Reg::I3cTcSyncTph, Reg::I3cTcSyncTu, Reg::I3cTcSyncOdr].map(|r| mock.register(r));
+    assert_eq!(regs, [1000, 10, 1]);
+    assert_eq!(block_on(imu.get_i3c_tc_sync_conf()).unwrap(), cfg);
+    assert!(block_on(imu.get_enabled_features()).unwrap().i3c_sync);
+    block_on(imu.disable_i3c_tc_sync()).unwrap();
+    assert!(!block_on(imu.get_enabled_features()).unwrap().i3c_sync);
+
+    // no_motion is INT_MAP1[1:0], tilt INT_MAP1[15:14].
+    let map = IntMap { no_motion: IntPin::Ibi, tilt: IntPin::Ibi, ..Default::default() };
+    block_on(imu.set_int_map(map)).unwrap();
+    assert_eq!(mock.register(Reg::IntMap1), 0x3 | 0x3 << 14);
+    assert_eq!(block_on(imu.get_int_map()).unwrap(), map);
+  }
+
+  let update = [Reg::Cmd as u8, 0x01, 0x02]; // I3cTcSyncUpdate (0x0201), little endian
+  assert!(Records::new(sink.as_bytes()).any(|r| r.kind == RecordKind::Write && r.data == update));
+}
+
+#[test]
+fn interrupt_status_clears_on_read() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+
+  mock.raise_int1(1 << 1);
+  assert!(block_on(imu.get_int1_status()).unwrap().any_motion);
+  assert!(!block_on(imu.get_int1_status()).unwrap().any_motion);
+}
+
+#[test]
+fn fifo_reads_longer_than_one_burst() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+
+  let words: Vec<u16> = (0..40).collect();
+  mock.push_fifo(&words);
+
+  let mut out = [0u8; 100];
+  assert_eq!(block_on(imu.read_fifo_bytes(&mut out)).unwrap(), 80);
+  for (i, w) in out[..80].chunks(2).enumerate() {
+    assert_eq!(u16::from_le_bytes([w[0], w[1]]), i as u16);
+  }
+  assert_eq!(mock.fifo_len(), 0);
+}
+
+#[test]
+fn bus_recovery_restores_configuration() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(false)).unwrap();
+  let cfg = AccelConfig { mode: AccelPowerMode::Normal, ..AccelConfig::default() };
+  block_on(imu.set_accel_conf(cfg)).unwrap();
+
+  mock.fail_transfers(3);
+  assert!(matches!(block_on(imu.get_id()), Err(Error::I2c(_))));
+  assert_eq!(imu.consecutive_i2c_errors(), 1);
+
+  mock.set_register(Reg::AccConf, 0);
+  block_on(imu.recover_bus(5)).unwrap();
+  assert_eq!(imu.consecutive_i2c_errors(), 0);
+  assert_eq!(block_on(imu.get_accel_conf()).unwrap(), cfg);
+}
+
+#[test]
+fn register_dump_decodes_and_restores() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(true)).unwrap();
+  let accel = AccelConfig { odr: OutputDataRate::Hz50, mode: AccelPowerMode::Normal, ..AccelConfig::default() };
+  block_on(imu.set_accel_conf(accel)).unwrap();
+  block_on(imu.set_tap_conf(TapConfig::default())).unwrap();
+  block_on(imu.set_spi_if(IoSpiIf { spi3: true })).unwrap();
+
+  let dump = block_on(imu.dump_registers()).unwrap();
+  assert_eq!(dump.chip_id, 0x43);
+  assert_eq!(dump.accel.value, Some(accel));
+  assert_eq!(dump.tap.value, Some(TapConfig::default()));
+  assert!(format!("{dump:?}").contains("Hz50"));
+
+  block_on(imu.soft_reset()).unwrap();
+  block_on(imu.restore_registers(&dump)).unwrap();
+  assert_eq!(block_on(imu.get_accel_conf()).unwrap(), accel);
+  assert!(mock.feature_engine_enabled());
+  assert_eq!(block_on(imu.get_tap_conf()).unwrap(), TapConfig::default());
+  assert!(block_on(imu.get_spi_if()).unwrap().spi3);
+}
+
+#[test]
+fn register_dump_skips_feature_memory_without_engine() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(false)).unwrap();
+  mock.set_feature_word(0x1E, 0x1234);
+
+  let dump = block_on(imu.dump_registers()).unwrap();
+  assert!(!dump.feature_engine_enabled());
+  assert_eq!(dump.tap.value, None);
+  assert_eq!(dump.step_watermark, None);
+  assert_eq!(dump.to_snapshot().tap, None);
+}
+
+#[cfg(feature = "events")]
+#[test]
+fn polling_source_reports_events() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+
+  mock.raise_int1(1 << 2);
+  assert_eq!(block_on(imu.wait_event()).unwrap().kind(), bmi323::EventKind::Flat);
+}
+
+#[test]
+fn typestate_forwards_offsets_io_and_calibration() {
+  let mock = MockBmi323::new();
+  let mut dev = block_on(Device::new(imu(&mock)).init()).unwrap();
+
+  let off = AccelOffsetGain { off_x: -12, gain_x: 3, off_y: 40, gain_y: -2, off_z: 0, gain_z: 1 };
+  block_on(dev.set_accel_offset_gain(off)).unwrap();
+  assert_eq!(block_on(dev.get_accel_offset_gain()).unwrap(), off);
+  block_on(dev.set_io_pad_strength(IoPadStrength { if_drv: 5 })).unwrap();
+  assert_eq!(block_on(dev.get_io_pad_strength()).unwrap().if_drv, 5);
+
+  let mut dev = block_on(dev.enable_feature_engine()).unwrap();
+  block_on(dev.set_gyro_sc_select(&[0x05, 0x00])).unwrap();
+  let mut out = [0u8; 2];
+  block_on(dev.get_gyro_sc_select(&mut out)).unwrap();
+  assert_eq!(out, [0x05, 0x00]);
+}
+
+#[test]
+fn typestate_rejects_calls_that_would_change_the_engine_state() {
+  let mock = MockBmi323::new();
+  let dev = Device::new(imu(&mock));
+
+  let mut cfg = DeviceConfig::default();
+  cfg.accel.mode = AccelPowerMode::Normal;
+  let mut dev = block_on(dev.apply_config(&cfg)).unwrap();
+  assert!(block_on(dev.read_back_config(&cfg)).unwrap().is_empty());
+
+  let profile = PowerProfile::LowPowerStepCounting;
+  assert!(matches!(block_on(dev.apply_power_profile(profile)), Err(Error::InvalidMode)));
+  assert!(!mock.feature_engine_enabled());
+  block_on(dev.apply_power_profile(PowerProfile::Normal)).unwrap();
+
+  let dump = block_on(dev.dump_registers()).unwrap();
+  let mut dev = block_on(dev.enable_feature_engine()).unwrap();
+  assert!(matches!(block_on(dev.restore_registers(&dump)), Err(Error::InvalidMode)));
+  block_on(dev.apply_power_profile(profile)).unwrap();
+  assert!(block_on(dev.get_enabled_features()).unwrap().step_counter);
+}
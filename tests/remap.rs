@@ -0,0 +1,126 @@
+use bmi323::{
+  accel::{AccelConfig, AccelPowerMode},
+  axis_remap::{AxisRemap, BoardDirection, Rotation},
+  fifo::{FifoConfig, FifoFrames},
+  gyro::{GyroConfig, GyroPowerMode},
+  testing::{block_on, MockBmi323},
+  ConfigError,
+};
+use micromath::vector::Vector3d;
+
+mod common;
+use common::imu;
+
+const DIRECTIONS: [BoardDirection; 6] = [
+  BoardDirection::Right,
+  BoardDirection::Left,
+  BoardDirection::Forward,
+  BoardDirection::Back,
+  BoardDirection::Up,
+  BoardDirection::Down,
+];
+
+#[test]
+fn every_mounting_maps_to_a_hardware_remap() {
+  let mut count = 0;
+  for x in DIRECTIONS {
+    for z in DIRECTIONS {
+      let Ok(map) = AxisRemap::from_directions(x, z) else {
+        assert_eq!(x.vector().map(i8::abs), z.vector().map(i8::abs));
+        continue;
+      };
+      count += 1;
+      let m = map.matrix();
+      assert_eq!(AxisRemap::from_matrix(m), Ok(map));
+      assert_eq!([m[0][0], m[1][0], m[2][0]], x.vector());
+      assert_eq!([m[0][2], m[1][2], m[2][2]], z.vector());
+      assert_eq!(Rotation::from_directions(x, z).unwrap().to_axis_remap(), Ok(map));
+    }
+  }
+  assert_eq!(count, 24);
+}
+
+#[test]
+fn mirrored_and_skewed_frames_are_rejected() {
+  let mirror = [[-1, 0, 0], [0, 1, 0], [0, 0, 1]];
+  assert_eq!(AxisRemap::from_matrix(mirror), Err(ConfigError::NotRotation));
+  assert_eq!(AxisRemap::from_matrix([[1, 0, 0], [1, 0, 0], [0, 0, 1]]), Err(ConfigError::NotRotation));
+  assert_eq!(Rotation::from_matrix([[1.0, 0.0, 0.0], [0.0, 2.0, 0.0], [0.0, 0.0, 1.0]]), Err(ConfigError::NotRotation));
+  let nan = [[f32::NAN, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+  assert_eq!(Rotation::from_matrix(nan), Err(ConfigError::NotRotation));
+}
+
+#[test]
+fn software_rotation_covers_non_axis_aligned_mounting() {
+  let h = core::f32::consts::FRAC_1_SQRT_2;
+  let rot = Rotation::from_matrix([[h, -h, 0.0], [h, h, 0.0], [0.0, 0.0, 1.0]]).unwrap();
+  assert_eq!(rot.to_axis_remap(), Err(ConfigError::NotAxisAligned));
+
+  let v = rot.apply_raw(Vector3d { x: 1000, y: 0, z: -500 });
+  assert_eq!((v.x, v.y, v.z), (707, 707, -500));
+}
+
+#[test]
+fn frame_transform_applies_to_register_and_fifo_data() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(false)).unwrap();
+  // Chip X points to the board's front, chip Z up: board = (-chip y, chip x, chip z).
+  let rot = Rotation::from_directions(BoardDirection::Forward, BoardDirection::Up).unwrap();
+  imu.set_frame_transform(Some(rot));
+
+  mock.set_accel_raw([100, 200, 300]);
+  mock.set_gyro_raw([-1, -2, -3]);
+  let v = block_on(imu.get_raw_accel_data()).unwrap();
+  assert_eq!((v.x, v.y, v.z), (-200, 100, 300));
+  let (a, g) = block_on(imu.get_raw_accel_gyro_data()).unwrap();
+  assert_eq!((a.x, a.y, a.z, g.x, g.y, g.z), (-200, 100, 300, 2, -1, -3));
+  let (af, _) = block_on(imu.get_accel_gyro_data()).unwrap();
+  assert_eq!(block_on(imu.get_accel_data()).unwrap(), af);
+
+  let cfg = FifoConfig { accel_en: true, gyro_en: true, time_en: true, ..Default::default() };
+  let words = [100, 200, 300, 0xFFFF, 0xFFFE, 0xFFFD, 42, 0x7F01, 0, 0, 1, 2, 3, 43, 7];
+  let mut frames = imu.parse_fifo(cfg, &words);
+  let f = frames.next().unwrap();
+  assert_eq!(f.accel.map(|v| (v.x, v.y, v.z)), Some((-200, 100, 300)));
+  assert_eq!(f.gyro.map(|v| (v.x, v.y, v.z)), Some((2, -1, -3)));
+  assert_eq!(f.time, Some(42));
+  let f = frames.next().unwrap();
+  assert_eq!(f.accel, None);
+  assert_eq!(f.gyro.map(|v| (v.x, v.y, v.z)), Some((-2, 1, 3)));
+  assert!(frames.next().is_none());
+  assert_eq!(frames.remainder(), &[7]);
+
+  let raw = FifoFrames::new(cfg, &words).next().unwrap();
+  assert_eq!(raw.accel.map(|v| (v.x, v.y, v.z)), Some((100, 200, 300)));
+}
+
+#[test]
+fn safe_remap_restores_sensor_configuration() {
+  let mock = MockBmi323::new();
+  let mut imu = imu(&mock);
+  block_on(imu.init(true)).unwrap();
+  let accel = AccelConfig { mode: AccelPowerMode::Normal, ..Default::default() };
+  let gyro = GyroConfig { mode: GyroPowerMode::Normal, ..Default::default() };
+  block_on(imu.set_accel_conf(accel)).unwrap();
+  block_on(imu.set_gyro_conf(gyro)).unwrap();
+
+  let map = AxisRemap::from_directions(BoardDirection::Forward, BoardDirection::Up).unwrap();
+  block_on(imu.set_axis_remap_safe(map)).unwrap();
+  assert_eq!(block_on(imu.get_axis_remap()).unwrap(), map);
+  assert_eq!(block_on(imu.get_accel_conf()).unwrap(), accel);
+  assert_eq!(block_on(imu.get_gyro_conf()).unwrap(), gyro);
+
+  // Axis-aligned mountings go to the chip; others stay in software.
+  block_on(imu.set_mounting(map.into())).unwrap();
+  assert_eq!(imu.frame_transform(), None);
+  let h = core::f32::consts::FRAC_1_SQRT_2;
+  let tilted = Rotation::from_matrix([[h, -h, 0.0], [h, h, 0.0], [0.0, 0.0, 1.0]]).unwrap();
+  block_on(imu.set_mounting(tilted)).unwrap();
+  assert_eq!(imu.frame_transform(), Some(tilted));
+  assert_eq!(block_on(imu.get_axis_remap()).unwrap(), AxisRemap::default());
+
+  mock.set_accel_raw([1000, 0, -500]);
+  let v = block_on(imu.get_raw_accel_data()).unwrap();
+  assert_eq!((v.x, v.y, v.z), (707, 707, -500));
+}
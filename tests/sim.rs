@@ -0,0 +1,144 @@
+use bmi323::{
+  accel::{AccelConfig, AccelPowerMode},
+  any_no_motion::AnyNoMotionConfig,
+  fifo::FifoConfig,
+  gyro::{GyroConfig, GyroPowerMode},
+  interrupt::{IntMap, IntPin},
+  power::PowerProfile,
+  tap::TapConfig,
+  testing::{
+    block_on,
+    sim::{Motion, Segment, SimDelay, Simulator},
+  },
+  Bmi323, OutputDataRate, Reg,
+};
+
+#[cfg(not(feature = "events"))]
+type Imu<'s, 'a> = Bmi323<&'s Simulator<'a>, SimDelay<'s, 'a>>;
+#[cfg(feature = "events")]
+type Imu<'s, 'a> = Bmi323<&'s Simulator<'a>, SimDelay<'s, 'a>, bmi323::Polling>;
+
+fn imu<'s, 'a>(sim: &'s Simulator<'a>) -> Imu<'s, 'a> {
+  #[cfg(not(feature = "events"))]
+  let mut imu = Bmi323::new(sim, sim.delay());
+  #[cfg(feature = "events")]
+  let mut imu = Bmi323::new(sim, sim.delay(), bmi323::Polling::from_millis(5));
+  block_on(imu.init(true)).unwrap();
+  imu
+}
+
+fn normal_100hz(imu: &mut Imu) {
+  let accel = AccelConfig { odr: OutputDataRate::Hz100, mode: AccelPowerMode::Normal, ..AccelConfig::default() };
+  let gyro = GyroConfig { odr: OutputDataRate::Hz100, mode: GyroPowerMode::Normal, ..GyroConfig::default() };
+  block_on(imu.set_accel_conf(accel)).unwrap();
+  block_on(imu.set_gyro_conf(gyro)).unwrap();
+}
+
+#[test]
+fn rotation_moves_gravity_between_axes() {
+  let script = [Segment::new(Motion::Rotate { rate_dps: [90.0, 0.0, 0.0] }, 1000)];
+  let sim = Simulator::new(&script);
+  let mut imu = imu(&sim);
+  normal_100hz(&mut imu);
+  sim.advance_ms(20);
+
+  let g = block_on(imu.get_accel_data()).unwrap();
+  assert!(g.z > 0.95, "starts flat: {g:?}");
+  let w = block_on(imu.get_gyro_data()).unwrap();
+  assert!((w.x - 90.0).abs() < 1.0, "{w:?}");
+
+  sim.advance_ms(980);
+  let g = block_on(imu.get_accel_data()).unwrap();
+  assert!(g.y > 0.95 && g.z.abs() < 0.1, "on its side: {g:?}");
+}
+
+#[test]
+fn fifo_fills_at_the_configured_odr() {
+  let sim = Simulator::new(&[]);
+  let mut imu = imu(&sim);
+  normal_100hz(&mut imu);
+  block_on(imu.set_fifo_config(FifoConfig { accel_en: true, gyro_en: true, ..FifoConfig::default() })).unwrap();
+  block_on(imu.fifo_flush()).unwrap();
+
+  sim.advance_ms(500);
+  let frames = block_on(imu.get_fifo_fill_level()).unwrap() / 6;
+  assert!((49..=51).contains(&frames), "{frames} frames");
+}
+
+#[test]
+fn any_and_no_motion_follow_the_script() {
+  let script = [
+    Segment::new(Motion::Rest, 500),
+    Segment::new(Motion::Rotate { rate_dps: [0.0, 0.0, 180.0] }, 500),
+    Segment::new(Motion::Rest, 1000),
+  ];
+  let sim = Simulator::new(&script);
+  let mut imu = imu(&sim);
+  let cfg = AnyNoMotionConfig { duration: 10, ..AnyNoMotionConfig::default() };
+  block_on(imu.enable_any_motion(true, true, true, cfg)).unwrap();
+  block_on(imu.enable_no_motion(true, true, true, cfg)).unwrap();
+  block_on(imu.set_int_map(IntMap { any_motion: IntPin::Int1, no_motion: IntPin::Int2, ..IntMap::default() })).unwrap();
+  normal_100hz(&mut imu);
+
+  sim.advance_ms(400);
+  assert!(block_on(imu.get_int2_status()).unwrap().no_motion);
+  assert!(!block_on(imu.get_int1_status()).unwrap().any_motion);
+
+  sim.advance_ms(400);
+  assert!(block_on(imu.get_int1_status()).unwrap().any_motion);
+
+  sim.advance_ms(600);
+  assert!(block_on(imu.get_int2_status()).unwrap().no_motion);
+}
+
+#[test]
+fn walking_counts_steps() {
+  let script = [Segment::new(Motion::Walk { steps_per_s: 2.0, amplitude_g: 0.3 }, 3000)];
+  let sim = Simulator::new(&script);
+  let mut imu = imu(&sim);
+  block_on(imu.apply_power_profile(PowerProfile::LowPowerStepCounting)).unwrap();
+  block_on(imu.set_int_map(IntMap { step_detector: IntPin::Int1, ..IntMap::default() })).unwrap();
+
+  sim.advance_ms(3000);
+  assert!(block_on(imu.get_int1_status()).unwrap().step_detector);
+  let count = sim.mock().register(Reg::FeatureIo2);
+  assert!((5..=6).contains(&count), "{count} steps");
+}
+
+#[cfg(feature = "events")]
+#[test]
+fn double_tap_reaches_the_event_stream() {
+  use bmi323::{interrupt::TapKind, Event, EventFilter, EventKind};
+
+  let script = [
+    Segment::new(Motion::Rest, 200),
+    Segment::new(Motion::Tap { axis: 2, peak_g: 2.0, count: 2 }, 500),
+  ];
+  let sim = Simulator::new(&script);
+  let mut imu = imu(&sim);
+  block_on(imu.enable_tap(true, true, false, TapConfig::default())).unwrap();
+  block_on(imu.set_int_map(IntMap { tap: IntPin::Int1, ..IntMap::default() })).unwrap();
+  normal_100hz(&mut imu);
+
+  let event = block_on(imu.wait_event_matching(EventFilter::only(EventKind::Tap))).unwrap();
+  assert!(matches!(event, Event::Tap { kind: TapKind::Double, .. }), "{event:?}");
+}
+
+#[cfg(not(feature = "events"))]
+#[test]
+fn double_tap_sets_extended_status() {
+  let script = [
+    Segment::new(Motion::Rest, 200),
+    Segment::new(Motion::Tap { axis: 2, peak_g: 2.0, count: 2 }, 500),
+  ];
+  let sim = Simulator::new(&script);
+  let mut imu = imu(&sim);
+  block_on(imu.enable_tap(true, true, false, TapConfig::default())).unwrap();
+  block_on(imu.set_int_map(IntMap { tap: IntPin::Int1, ..IntMap::default() })).unwrap();
+  normal_100hz(&mut imu);
+
+  sim.advance_ms(800);
+  assert!(block_on(imu.get_int1_status()).unwrap().tap);
+  let ext = block_on(imu.get_feature_event_ext()).unwrap();
+  assert!(ext.d_tap && !ext.s_tap);
+}
@@ -0,0 +1,94 @@
+use bmi323::{
+  accel::{AccelConfig, AccelPowerMode},
+  testing::{block_on, MockBmi323},
+  trace::{RecordKind, Recorder, Records, Replay, ReplayError, SliceSink, MAX_PAYLOAD},
+  Error, OutputDataRate, Reg,
+};
+use embedded_hal_async::i2c::I2c;
+
+mod common;
+use common::imu;
+
+fn accel() -> AccelConfig {
+  AccelConfig { mode: AccelPowerMode::Normal, ..AccelConfig::default() }
+}
+
+/// Record `init` plus an accel configuration and one sample read.
+fn record(mock: &MockBmi323, buf: &mut [u8]) -> usize {
+  let mut sink = SliceSink::new(buf);
+  let mut t = 0;
+  let mut imu = imu(Recorder::new(mock, &mut sink, || {
+    t += 100;
+    t
+  }));
+  block_on(imu.init(false)).unwrap();
+  block_on(imu.set_accel_conf(accel())).unwrap();
+  mock.set_accel_raw([1, 2, 3]);
+  block_on(imu.get_raw_accel_data()).unwrap();
+  sink.as_bytes().len()
+}
+
+#[test]
+fn replay_reproduces_a_recorded_session() {
+  let mock = MockBmi323::new();
+  let mut buf = [0u8; 2048];
+  let len = record(&mock, &mut buf);
+  let trace = &buf[..len];
+
+  let records: Vec<_> = Records::new(trace).collect();
+  assert!(records.windows(2).all(|w| w[0].timestamp_us <= w[1].timestamp_us));
+  assert_eq!(records[0].kind, RecordKind::Write);
+
+  let mut imu = imu(Replay::new(trace));
+  block_on(imu.init(false)).unwrap();
+  block_on(imu.set_accel_conf(accel())).unwrap();
+  let v = block_on(imu.get_raw_accel_data()).unwrap();
+  assert_eq!((v.x, v.y, v.z), (1, 2, 3));
+}
+
+#[test]
+fn replay_reports_divergence() {
+  let mock = MockBmi323::new();
+  let mut buf = [0u8; 2048];
+  let len = record(&mock, &mut buf);
+
+  let mut imu = imu(Replay::new(&buf[..len]));
+  block_on(imu.init(false)).unwrap();
+  let other = AccelConfig { odr: OutputDataRate::Hz100, ..accel() };
+  assert!(matches!(block_on(imu.set_accel_conf(other)), Err(Error::I2c(ReplayError::Mismatch { .. }))));
+}
+
+#[test]
+fn failed_transfers_are_recorded_and_replayed() {
+  let mock = MockBmi323::new();
+  let mut buf = [0u8; 256];
+  let mut sink = SliceSink::new(&mut buf);
+  {
+    let mut imu = imu(Recorder::new(&mock, &mut sink, || 0));
+    mock.fail_transfers(1);
+    assert!(block_on(imu.get_id()).is_err());
+    assert_eq!(block_on(imu.get_id()).unwrap(), 0x43);
+  }
+
+  let mut imu = imu(Replay::new(sink.as_bytes()));
+  assert!(matches!(block_on(imu.get_id()), Err(Error::I2c(ReplayError::Recorded))));
+  assert_eq!(block_on(imu.get_id()).unwrap(), 0x43);
+  assert!(matches!(block_on(imu.get_id()), Err(Error::I2c(ReplayError::Exhausted))));
+}
+
+#[test]
+fn oversized_payloads_are_dropped_not_truncated() {
+  let mock = MockBmi323::new();
+  let mut buf = vec![0u8; 2 * MAX_PAYLOAD];
+  let mut sink = SliceSink::new(&mut buf);
+  let mut rec = Recorder::new(&mock, &mut sink, || 0);
+
+  let mut data = vec![0u8; MAX_PAYLOAD + 1];
+  block_on(rec.write_read(0x68, &[Reg::FifoData as u8], &mut data)).unwrap();
+  assert_eq!(rec.dropped(), 1);
+  block_on(rec.write_read(0x68, &[Reg::ChipId as u8], &mut data[..4])).unwrap();
+
+  // The write records and the short read still decode; the oversized read is absent.
+  let kinds: Vec<_> = Records::new(sink.as_bytes()).map(|r| (r.kind, r.data.len())).collect();
+  assert_eq!(kinds, [(RecordKind::Write, 1), (RecordKind::Write, 1), (RecordKind::Read, 4)]);
+}
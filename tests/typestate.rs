@@ -0,0 +1,59 @@
+use std::{fs, path::Path};
+
+/// Driver methods the typestate deliberately does not forward.
+const NOT_FORWARDED: &[&str] = &[
+  // Constructors: wrap the result with `Device::new` instead.
+  "new",
+  "probe",
+  // Raw writes can change the tracked state behind the wrapper's back.
+  "write_register",
+];
+
+/// Names of the public methods declared in `impl ... Bmi323<..>` blocks of `src`.
+fn driver_methods(src: &str, out: &mut Vec<String>) {
+  let mut in_impl = false;
+  for line in src.lines() {
+    if line.starts_with("impl") && line.contains("Bmi323<") && !line.contains(" for ") {
+      in_impl = true;
+    } else if line.starts_with('}') {
+      in_impl = false;
+    } else if in_impl {
+      let decl = line
+        .strip_prefix("  pub async fn ")
+        .or_else(|| line.strip_prefix("  pub fn "));
+      if let Some(name) = decl.and_then(|d| d.split(['(', '<']).next()) {
+        out.push(name.to_string());
+      }
+    }
+  }
+}
+
+fn visit(dir: &Path, out: &mut Vec<String>) {
+  for entry in fs::read_dir(dir).unwrap() {
+    let path = entry.unwrap().path();
+    let name = path.file_name().unwrap().to_str().unwrap();
+    if path.is_dir() {
+      if name != "bin" && name != "testing" {
+        visit(&path, out);
+      }
+    } else if name.ends_with(".rs") && name != "typestate.rs" {
+      driver_methods(&fs::read_to_string(&path).unwrap(), out);
+    }
+  }
+}
+
+#[test]
+fn every_driver_method_is_forwarded() {
+  let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+  let mut methods = Vec::new();
+  visit(&src, &mut methods);
+  assert!(methods.iter().any(|m| m == "set_accel_conf"), "no driver methods found");
+
+  let typestate = fs::read_to_string(src.join("typestate.rs")).unwrap();
+  let missing: Vec<_> = methods
+    .iter()
+    .filter(|m| !NOT_FORWARDED.contains(&m.as_str()))
+    .filter(|m| !typestate.contains(&format!("fn {m}(")) && !typestate.contains(&format!("fn {m}<")))
+    .collect();
+  assert!(missing.is_empty(), "not forwarded by typestate::Device: {missing:?}");
+}